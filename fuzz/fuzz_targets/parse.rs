@@ -0,0 +1,13 @@
+#![no_main]
+
+use irc::message::GenericIrcCommand;
+use irc::message::IrcMessage;
+use libfuzzer_sys::fuzz_target;
+
+// Lines come straight from the network, so parsing may fail but must never panic
+fuzz_target!(|data: &[u8]| {
+    let line = String::from_utf8_lossy(data);
+
+    let _ = IrcMessage::try_from(line.as_ref());
+    let _ = GenericIrcCommand::try_from(line.trim_end_matches("\r\n"));
+});
@@ -0,0 +1,92 @@
+use std::net::SocketAddr;
+use std::net::ToSocketAddrs;
+
+const DEFAULT_PORT: u16 = 6667;
+
+// A server address written the way people put them in config files, for the forms plain
+// ToSocketAddrs gets wrong. Accepted syntaxes, with the port defaulting to 6667:
+//
+//   irc.example.net            irc.example.net:6667
+//   127.0.0.1                  127.0.0.1:6667
+//   ::1                        [::1]              [::1]:6667
+//   fe80::1%eth0               [fe80::1%eth0]     [fe80::1%eth0]:6667
+//
+// Zones may be interface names or indexes. A bare IPv6 literal can't carry a port, since
+// its last group would be read as one
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ServerAddress<'a>(pub &'a str);
+
+impl ToSocketAddrs for ServerAddress<'_> {
+    type Iter = std::vec::IntoIter<SocketAddr>;
+
+    fn to_socket_addrs(&self) -> Result<Self::Iter, std::io::Error> {
+        let (host, port) = split_host_port(self.0)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("Invalid server address \"{}\": {}", self.0, e)))?;
+
+        let port = match port {
+            Some(port) => port.parse::<u16>()
+                .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("Invalid port \"{}\"", port)))?,
+            None => DEFAULT_PORT,
+        };
+
+        // The resolver understands zones, including interface names, as long as the
+        // address isn't bracketed
+        (host, port).to_socket_addrs()
+    }
+}
+
+// Splits "host:port" into its parts, IPv6 hosts are returned without their brackets
+pub(crate) fn split_host_port(address: &str) -> Result<(&str, Option<&str>), &'static str> {
+    let (host, port) = match address.strip_prefix('[') {
+        Some(bracketed) => match bracketed.split_once(']') {
+            Some((host, "")) => (host, None),
+            Some((host, port)) => match port.strip_prefix(':') {
+                Some(port) => (host, Some(port)),
+                None => return Err("unexpected characters after host"),
+            },
+            None => return Err("unclosed \"[\" in host"),
+        },
+        // More than one colon can only be a bare IPv6 literal
+        None if address.matches(':').count() > 1 => (address, None),
+        None => match address.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (address, None),
+        },
+    };
+
+    if host.is_empty() {
+        return Err("missing host");
+    }
+
+    Ok((host, port))
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(address: &str) -> Option<SocketAddr> {
+        ServerAddress(address).to_socket_addrs().ok()?.next()
+    }
+
+    #[test]
+    fn server_address() {
+        assert_eq!(resolve("127.0.0.1"), Some("127.0.0.1:6667".parse().unwrap()));
+        assert_eq!(resolve("127.0.0.1:7000"), Some("127.0.0.1:7000".parse().unwrap()));
+        assert_eq!(resolve("::1"), Some("[::1]:6667".parse().unwrap()));
+        assert_eq!(resolve("[::1]"), Some("[::1]:6667".parse().unwrap()));
+        assert_eq!(resolve("[::1]:7000"), Some("[::1]:7000".parse().unwrap()));
+        assert_eq!(resolve("fe80::1%1"), Some("[fe80::1%1]:6667".parse().unwrap()));
+        assert_eq!(resolve("[fe80::1%1]:7000"), Some("[fe80::1%1]:7000".parse().unwrap()));
+
+        // The loopback interface always has index 1 on Linux
+        #[cfg(target_os = "linux")]
+        assert_eq!(resolve("[fe80::1%lo]:7000"), Some("[fe80::1%1]:7000".parse().unwrap()));
+
+        for address in ["", "[::1", "[::1]7000", "127.0.0.1:port", ":6667"] {
+            assert!(resolve(address).is_none());
+        }
+    }
+}
@@ -0,0 +1,60 @@
+// How the server folds case when comparing nicks and channel names, advertised by
+// the CASEMAPPING ISUPPORT token. RFC 1459 is assumed until the server says otherwise
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum CaseMapping {
+    Ascii,
+    #[default]
+    Rfc1459,
+    // Like rfc1459, but "~" and "^" are not treated as the same character
+    StrictRfc1459,
+}
+
+impl CaseMapping {
+    pub fn from_token(token: &str) -> Option<Self> {
+        match token {
+            "ascii" => Some(Self::Ascii),
+            "rfc1459" => Some(Self::Rfc1459),
+            "strict-rfc1459" => Some(Self::StrictRfc1459),
+            _ => None,
+        }
+    }
+
+    fn lower(self, c: char) -> char {
+        match (self, c) {
+            (_, 'A'..='Z') => c.to_ascii_lowercase(),
+            (Self::Rfc1459 | Self::StrictRfc1459, '[') => '{',
+            (Self::Rfc1459 | Self::StrictRfc1459, ']') => '}',
+            (Self::Rfc1459 | Self::StrictRfc1459, '\\') => '|',
+            (Self::Rfc1459, '~') => '^',
+            _ => c,
+        }
+    }
+}
+
+pub fn irc_lower(casemapping: CaseMapping, value: &str) -> String {
+    value.chars().map(|c| casemapping.lower(c)).collect()
+}
+
+pub fn irc_eq(casemapping: CaseMapping, a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.chars().zip(b.chars()).all(|(a, b)| casemapping.lower(a) == casemapping.lower(b))
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn casemapping() {
+        assert!(irc_eq(CaseMapping::Rfc1459, "Foo[bar]", "foo{bar}"));
+        assert!(irc_eq(CaseMapping::Rfc1459, "nick\\~", "NICK|^"));
+        assert!(!irc_eq(CaseMapping::StrictRfc1459, "nick~", "nick^"));
+        assert!(irc_eq(CaseMapping::StrictRfc1459, "Foo[bar]", "foo{bar}"));
+        assert!(!irc_eq(CaseMapping::Ascii, "Foo[bar]", "foo{bar}"));
+        assert!(irc_eq(CaseMapping::Ascii, "#Rust", "#rust"));
+
+        assert_eq!(irc_lower(CaseMapping::Rfc1459, "#Rust[Dev]"), "#rust{dev}".to_string());
+        assert_eq!(irc_lower(CaseMapping::Ascii, "#Rust[Dev]"), "#rust[dev]".to_string());
+    }
+}
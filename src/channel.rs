@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::time::SystemTime;
+
+use crate::casemapping::irc_lower;
+use crate::casemapping::CaseMapping;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Channel {
+    pub name: String,
+    pub topic: Option<String>,
+    // Nick or full mask depending on the server
+    pub topic_set_by: Option<String>,
+    pub topic_set_at: Option<SystemTime>,
+    // Keyed by nick lowercased with the server's casemapping
+    pub members: HashMap<String, Member>,
+    // Set modes that aren't lists or member statuses, with their parameter if they have one
+    pub modes: HashMap<char, Option<String>>,
+    // From RPL_CREATIONTIME, servers usually send it along with the channel's modes
+    pub created: Option<SystemTime>,
+}
+
+impl Channel {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            topic: None,
+            topic_set_by: None,
+            topic_set_at: None,
+            members: HashMap::new(),
+            modes: HashMap::new(),
+            created: None,
+        }
+    }
+
+    // The key (+k), if we've seen it. Servers may show "*" to users who can't see the key
+    pub fn key(&self) -> Option<&str> {
+        self.modes.get(&'k')?.as_deref()
+    }
+
+    // The user limit (+l)
+    pub fn limit(&self) -> Option<u32> {
+        self.modes.get(&'l')?.as_deref()?.parse().ok()
+    }
+
+    // Applies a MODE change, list modes such as bans aren't tracked here
+    pub fn apply_mode(&mut self, casemapping: CaseMapping, chanmodes: &ChanModes, prefixes: &[(char, char)], change: &ModeChange) {
+        if prefixes.iter().any(|(mode, _)| *mode == change.mode) {
+            let Some(member) = change.param.as_ref().and_then(|m| self.members.get_mut(&irc_lower(casemapping, m))) else {
+                return;
+            };
+
+            if change.add {
+                member.modes.insert(change.mode);
+            } else {
+                member.modes.remove(&change.mode);
+            }
+        } else if !chanmodes.list.contains(change.mode) {
+            if change.add {
+                self.modes.insert(change.mode, change.param.clone());
+            } else {
+                self.modes.remove(&change.mode);
+            }
+        }
+    }
+}
+
+// The CHANMODES ISUPPORT token, which modes take a parameter and when
+#[derive(Debug, PartialEq, Clone)]
+pub struct ChanModes {
+    // Type A, e.g. bans, always take a parameter
+    pub list: String,
+    // Type B, e.g. the key, always take a parameter
+    pub always: String,
+    // Type C, e.g. the limit, only take a parameter when set
+    pub on_set: String,
+    // Type D, never take a parameter
+    pub flags: String,
+}
+
+impl ChanModes {
+    // e.g. "beI,k,l,imnpst", extra groups from newer servers are ignored
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut groups = value.split(',');
+
+        Some(Self {
+            list: groups.next()?.to_string(),
+            always: groups.next()?.to_string(),
+            on_set: groups.next()?.to_string(),
+            flags: groups.next()?.to_string(),
+        })
+    }
+}
+
+// RFC 1459 modes, until the server says otherwise
+impl Default for ChanModes {
+    fn default() -> Self {
+        Self {
+            list: "b".to_string(),
+            always: "k".to_string(),
+            on_set: "l".to_string(),
+            flags: "imnpst".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ModeChange {
+    pub add: bool,
+    pub mode: char,
+    pub param: Option<String>,
+}
+
+// Splits a mode string like "+ov-k Jimmy Bobby key" into single changes, pairing each
+// mode with its parameter. Status modes from `prefixes` always take a parameter
+pub fn parse_mode_changes(modes: &str, mut params: impl Iterator<Item = String>, chanmodes: &ChanModes, prefixes: &[(char, char)]) -> Vec<ModeChange> {
+    let mut changes = Vec::new();
+    let mut add = true;
+
+    for mode in modes.chars() {
+        match mode {
+            '+' => add = true,
+            '-' => add = false,
+            _ => {
+                let takes_param = chanmodes.list.contains(mode)
+                    || chanmodes.always.contains(mode)
+                    || (add && chanmodes.on_set.contains(mode))
+                    || prefixes.iter().any(|(prefix_mode, _)| *prefix_mode == mode);
+
+                changes.push(ModeChange {
+                    add,
+                    mode,
+                    param: if takes_param { params.next() } else { None },
+                });
+            },
+        }
+    }
+
+    changes
+}
+
+// An entry of a channel list mode such as bans (+b)
+#[derive(Debug, PartialEq, Clone)]
+pub struct ListEntry {
+    pub mask: String,
+    pub set_by: Option<String>,
+    pub set_at: Option<SystemTime>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Member {
+    pub nick: String,
+    // Status modes such as 'o' and 'v'. Without multi-prefix servers only report the highest
+    pub modes: HashSet<char>,
+    // Unknown until the user does something that shows their hostmask, e.g. joins
+    pub user: Option<String>,
+    pub host: Option<String>,
+    // Services account, needs account-notify to be kept up to date
+    pub account: Option<String>,
+}
+
+impl Member {
+    // `prefixes` maps status modes to their prefix, e.g. ('o', '@'), as advertised in ISUPPORT PREFIX.
+    // Entries are bare nicks, or "nick!user@host" with the userhost-in-names capability
+    pub fn parse(prefixes: &[(char, char)], entry: &str) -> Self {
+        let source = entry.trim_start_matches(|c| prefixes.iter().any(|(_, prefix)| *prefix == c));
+
+        let (nick, userhost) = match source.split_once('!') {
+            Some((nick, userhost)) => (nick, userhost.split_once('@')),
+            None => (source, None),
+        };
+
+        Self {
+            nick: nick.to_string(),
+            modes: entry[..entry.len() - source.len()].chars()
+                .filter_map(|c| prefixes.iter().find(|(_, prefix)| *prefix == c).map(|(mode, _)| *mode))
+                .collect(),
+            user: userhost.map(|(user, _)| user.to_string()),
+            host: userhost.map(|(_, host)| host.to_string()),
+            account: None,
+        }
+    }
+}
+
+// Parses the value of the PREFIX ISUPPORT token, e.g. "(ov)@+"
+pub fn parse_prefixes(value: &str) -> Option<Vec<(char, char)>> {
+    if value.is_empty() {
+        return Some(vec![]);
+    }
+
+    let (modes, prefixes) = value.strip_prefix('(')?.split_once(')')?;
+
+    if modes.chars().count() != prefixes.chars().count() {
+        return None;
+    }
+
+    Some(modes.chars().zip(prefixes.chars()).collect())
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn members() {
+        let prefixes = parse_prefixes("(qaohv)~&@%+").unwrap();
+
+        assert_eq!(Member::parse(&prefixes, "@%+Jimmy"), Member {
+            nick: "Jimmy".to_string(),
+            modes: HashSet::from(['o', 'h', 'v']),
+            user: None,
+            host: None,
+            account: None,
+        });
+
+        assert_eq!(Member::parse(&prefixes, "Jimmy"), Member {
+            nick: "Jimmy".to_string(),
+            modes: HashSet::new(),
+            user: None,
+            host: None,
+            account: None,
+        });
+
+        assert_eq!(Member::parse(&prefixes, "@+Jimmy!jim@example.com"), Member {
+            nick: "Jimmy".to_string(),
+            modes: HashSet::from(['o', 'v']),
+            user: Some("jim".to_string()),
+            host: Some("example.com".to_string()),
+            account: None,
+        });
+
+        // Only the modes the server advertised are prefixes
+        assert_eq!(Member::parse(&parse_prefixes("(ov)@+").unwrap(), "@%Jimmy").nick, "%Jimmy".to_string());
+
+        assert_eq!(parse_prefixes(""), Some(vec![]));
+        assert_eq!(parse_prefixes("(ov)@"), None);
+    }
+
+    #[test]
+    fn modes() {
+        let chanmodes = ChanModes::parse("beI,k,l,imnpst").unwrap();
+        let prefixes = parse_prefixes("(ov)@+").unwrap();
+
+        let changes = parse_mode_changes("+olb-lk+m", ["Jimmy", "10", "*!*@spam.example", "key"].into_iter().map(|m| m.to_string()), &chanmodes, &prefixes);
+        assert_eq!(changes, vec![
+            ModeChange { add: true, mode: 'o', param: Some("Jimmy".to_string()) },
+            ModeChange { add: true, mode: 'l', param: Some("10".to_string()) },
+            ModeChange { add: true, mode: 'b', param: Some("*!*@spam.example".to_string()) },
+            ModeChange { add: false, mode: 'l', param: None },
+            ModeChange { add: false, mode: 'k', param: Some("key".to_string()) },
+            ModeChange { add: true, mode: 'm', param: None },
+        ]);
+
+        let mut channel = Channel::new("#rust".to_string());
+        channel.members.insert("jimmy".to_string(), Member::parse(&prefixes, "Jimmy"));
+
+        for change in &parse_mode_changes("+oklb", ["JIMMY", "key", "10", "*!*@*"].into_iter().map(|m| m.to_string()), &chanmodes, &prefixes) {
+            channel.apply_mode(CaseMapping::default(), &chanmodes, &prefixes, change);
+        }
+
+        assert_eq!(channel.members["jimmy"].modes, HashSet::from(['o']));
+        // The ban doesn't end up among the scalar modes
+        assert_eq!(channel.modes, HashMap::from([('k', Some("key".to_string())), ('l', Some("10".to_string()))]));
+        assert_eq!(channel.key(), Some("key"));
+        assert_eq!(channel.limit(), Some(10));
+
+        for change in &parse_mode_changes("-ol", ["Jimmy"].into_iter().map(|m| m.to_string()), &chanmodes, &prefixes) {
+            channel.apply_mode(CaseMapping::default(), &chanmodes, &prefixes, change);
+        }
+
+        assert!(channel.members["jimmy"].modes.is_empty());
+        assert_eq!(channel.modes, HashMap::from([('k', Some("key".to_string()))]));
+        assert_eq!(channel.limit(), None);
+
+        // Servers echo the key back on -k, or a "*"
+        for change in &parse_mode_changes("-k", ["*"].into_iter().map(|m| m.to_string()), &chanmodes, &prefixes) {
+            channel.apply_mode(CaseMapping::default(), &chanmodes, &prefixes, change);
+        }
+
+        assert_eq!(channel.key(), None);
+        assert!(channel.modes.is_empty());
+    }
+}
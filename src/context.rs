@@ -1,11 +1,37 @@
 use std::sync::Arc;
 
+use tokio::sync::mpsc;
+
 use crate::client::Motd;
+use crate::message::IrcCommand;
 
 #[derive(Debug, Clone)]
 pub struct Context {
     pub status: Arc<ConnectionStatus>,
     pub motd: Arc<Motd>,
+    // Feeds the connection the event came from
+    pub(crate) outgoing: Option<mpsc::UnboundedSender<IrcCommand>>,
+}
+
+impl Context {
+    // Without a connection to send on, e.g. for testing handlers
+    pub fn new(status: ConnectionStatus, motd: Motd) -> Self {
+        Self {
+            status: Arc::new(status),
+            motd: Arc::new(motd),
+            outgoing: None,
+        }
+    }
+
+    // Handlers can't wait, so the command is queued and sent in order by the client.
+    // Fails once the connection the event came from is gone
+    pub fn send(&self, command: IrcCommand) -> Result<(), std::io::Error> {
+        match &self.outgoing {
+            Some(outgoing) => outgoing.send(command)
+                .map_err(|_| std::io::Error::new(std::io::ErrorKind::NotConnected, "Connection is closed")),
+            None => Err(std::io::Error::new(std::io::ErrorKind::NotConnected, "Context has no connection")),
+        }
+    }
 }
 
 
@@ -13,5 +39,24 @@ pub struct Context {
 pub enum ConnectionStatus {
     Connecting,
     Connected,
-    Disconnected,
+    Disconnected(DisconnectReason),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum DisconnectReason {
+    // The text of the server's ERROR message
+    ServerError(String),
+    // The connection ended without an ERROR
+    ConnectionClosed,
+    // An operator or services removed us with KILL, whatever the server sent after it
+    Killed {
+        by: Option<String>,
+        reason: String,
+    },
+    // Writing a reply from the read task failed, e.g. a PONG. Holds the error's text
+    WriteFailed(String),
+    // Reading from the connection failed, e.g. it was reset. Holds the error's text
+    ReadFailed(String),
+    // Nothing was received within ClientBuilder::read_timeout
+    PingTimeout,
 }
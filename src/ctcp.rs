@@ -0,0 +1,37 @@
+const DELIMITER: char = '\x01';
+
+// "\x01ACTION text\x01", what clients send for /me
+pub fn encode_action(text: &str) -> String {
+    format!("{}ACTION {}{}", DELIMITER, text, DELIMITER)
+}
+
+// Some clients leave off the closing delimiter, so it's optional here
+pub fn decode_action(text: &str) -> Option<&str> {
+    let body = text.strip_prefix(DELIMITER)?;
+    let body = body.strip_suffix(DELIMITER).unwrap_or(body);
+
+    match body.strip_prefix("ACTION") {
+        Some("") => Some(""),
+        Some(rest) => rest.strip_prefix(' '),
+        None => None,
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn action() {
+        assert_eq!(encode_action("waves"), "\x01ACTION waves\x01".to_string());
+        assert_eq!(decode_action(&encode_action("waves at everyone")), Some("waves at everyone"));
+        assert_eq!(decode_action(&encode_action("")), Some(""));
+
+        assert_eq!(decode_action("\x01ACTION waves"), Some("waves"));
+        assert_eq!(decode_action("\x01ACTIONS\x01"), None);
+        assert_eq!(decode_action("\x01VERSION\x01"), None);
+        assert_eq!(decode_action("ACTION waves"), None);
+    }
+}
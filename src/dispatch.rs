@@ -0,0 +1,168 @@
+use std::sync::Arc;
+
+use crate::context::Context;
+use crate::event::Event;
+use crate::event_handler::EventHandler;
+
+type EventCallback = Box<dyn Fn(Arc<Context>, &Event) + Send + Sync>;
+type EventFilter = fn(&Event) -> bool;
+
+// Dispatches events to callbacks registered per kind of event, the Event level counterpart
+// to CommandRouter. Events no callback is registered for are ignored
+#[derive(Default)]
+pub struct DispatchHandler {
+    routes: Vec<(EventFilter, EventCallback)>,
+    any: Vec<EventCallback>,
+}
+
+impl DispatchHandler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Called for every event `filter` returns true for, e.g. `|e| matches!(e, Event::Topic { .. })`
+    pub fn on<F: Fn(Arc<Context>, &Event) + Send + Sync + 'static>(mut self, filter: EventFilter, callback: F) -> Self {
+        self.routes.push((filter, Box::new(callback)));
+        self
+    }
+
+    // Called for every event, after the callbacks registered for its kind
+    pub fn on_any<F: Fn(Arc<Context>, &Event) + Send + Sync + 'static>(mut self, callback: F) -> Self {
+        self.any.push(Box::new(callback));
+        self
+    }
+
+    pub fn on_registered<F: Fn(Arc<Context>, &Event) + Send + Sync + 'static>(self, callback: F) -> Self {
+        self.on(|event| matches!(event, Event::Registered(_)), callback)
+    }
+
+    pub fn on_message<F: Fn(Arc<Context>, &Event) + Send + Sync + 'static>(self, callback: F) -> Self {
+        self.on(|event| matches!(event, Event::Message { .. }), callback)
+    }
+
+    pub fn on_notice<F: Fn(Arc<Context>, &Event) + Send + Sync + 'static>(self, callback: F) -> Self {
+        self.on(|event| matches!(event, Event::Notice { .. }), callback)
+    }
+
+    pub fn on_action<F: Fn(Arc<Context>, &Event) + Send + Sync + 'static>(self, callback: F) -> Self {
+        self.on(|event| matches!(event, Event::Action { .. }), callback)
+    }
+
+    pub fn on_join<F: Fn(Arc<Context>, &Event) + Send + Sync + 'static>(self, callback: F) -> Self {
+        self.on(|event| matches!(event, Event::Joined { .. }), callback)
+    }
+
+    pub fn on_part<F: Fn(Arc<Context>, &Event) + Send + Sync + 'static>(self, callback: F) -> Self {
+        self.on(|event| matches!(event, Event::Parted { .. }), callback)
+    }
+
+    pub fn on_kick<F: Fn(Arc<Context>, &Event) + Send + Sync + 'static>(self, callback: F) -> Self {
+        self.on(|event| matches!(event, Event::Kicked { .. }), callback)
+    }
+
+    pub fn on_quit<F: Fn(Arc<Context>, &Event) + Send + Sync + 'static>(self, callback: F) -> Self {
+        self.on(|event| matches!(event, Event::Quit { .. }), callback)
+    }
+
+    pub fn on_nick_change<F: Fn(Arc<Context>, &Event) + Send + Sync + 'static>(self, callback: F) -> Self {
+        self.on(|event| matches!(event, Event::NickChange { .. }), callback)
+    }
+
+    pub fn on_topic<F: Fn(Arc<Context>, &Event) + Send + Sync + 'static>(self, callback: F) -> Self {
+        self.on(|event| matches!(event, Event::Topic { .. }), callback)
+    }
+
+    pub fn on_invite<F: Fn(Arc<Context>, &Event) + Send + Sync + 'static>(self, callback: F) -> Self {
+        self.on(|event| matches!(event, Event::Invited { .. }), callback)
+    }
+
+    pub fn on_ping<F: Fn(Arc<Context>, &Event) + Send + Sync + 'static>(self, callback: F) -> Self {
+        self.on(|event| matches!(event, Event::Ping(_)), callback)
+    }
+
+    pub fn on_raw_message<F: Fn(Arc<Context>, &Event) + Send + Sync + 'static>(self, callback: F) -> Self {
+        self.on(|event| matches!(event, Event::RawMessage(_)), callback)
+    }
+
+    pub fn dispatch(&self, ctx: Arc<Context>, event: &Event) {
+        for (filter, callback) in &self.routes {
+            if filter(event) {
+                callback(ctx.clone(), event);
+            }
+        }
+
+        for callback in &self.any {
+            callback(ctx.clone(), event);
+        }
+    }
+}
+
+impl EventHandler for DispatchHandler {
+    fn on_event(&self, ctx: Arc<Context>, event: Event) {
+        self.dispatch(ctx, &event);
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::client::Motd;
+    use crate::context::ConnectionStatus;
+
+    use super::*;
+
+    #[test]
+    fn dispatch() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+
+        let handler = {
+            let messages = seen.clone();
+            let joins = seen.clone();
+            let topics = seen.clone();
+            let any = seen.clone();
+
+            DispatchHandler::new()
+                .on_message(move |_, event| {
+                    if let Event::Message { text, .. } = event {
+                        messages.lock().unwrap().push(format!("message {}", text));
+                    }
+                })
+                .on_join(move |_, event| {
+                    if let Event::Joined { channel, nick, .. } = event {
+                        joins.lock().unwrap().push(format!("join {} {}", nick, channel));
+                    }
+                })
+                .on(|event| matches!(event, Event::Topic { .. }), move |_, _| topics.lock().unwrap().push("topic".to_string()))
+                .on_any(move |_, _| any.lock().unwrap().push("any".to_string()))
+        };
+
+        let ctx = Arc::new(Context::new(ConnectionStatus::Connected, Motd::Empty));
+
+        handler.on_event(ctx.clone(), Event::Message {
+            from: Some("Bob".to_string()),
+            target: "#rust".to_string(),
+            text: "hello".to_string(),
+            stripped_text: None,
+            msgid: None,
+            time: None,
+            reply_to: None,
+        });
+        handler.on_event(ctx.clone(), Event::Joined { channel: "#rust".to_string(), nick: "Bob".to_string(), is_self: false });
+        handler.on_event(ctx.clone(), Event::Topic { channel: "#rust".to_string(), topic: None });
+        // Nothing registered but on_any
+        handler.on_event(ctx.clone(), Event::Ping("irc.example.net".to_string()));
+
+        assert_eq!(*seen.lock().unwrap(), vec![
+            "message hello", "any",
+            "join Bob #rust", "any",
+            "topic", "any",
+            "any",
+        ]);
+
+        // Unregistered kinds are ignored without on_any
+        DispatchHandler::new().on_event(ctx, Event::StatusChange);
+    }
+}
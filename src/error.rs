@@ -5,6 +5,8 @@ pub enum Error {
     NoMatch(String),
     NoCommand(String),
     Invalid,
+    ParseInt(String),
+    LineTooLong,
 }
 
 impl Display for Error {
@@ -16,7 +18,9 @@ impl Display for Error {
             Error::NoCommand(msg) => {
                 write!(f, "Message \"{}\" is missing command!", msg)
             },
-            Error::Invalid => write!(f, "Invalid string!")
+            Error::Invalid => write!(f, "Invalid string!"),
+            Error::ParseInt(value) => write!(f, "\"{}\" is not a valid number!", value),
+            Error::LineTooLong => write!(f, "Line is longer than 512 bytes!"),
         }
     }
 }
@@ -1,15 +1,283 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use crate::channel::ListEntry;
+use crate::channel::Member;
+use crate::channel::ModeChange;
+use crate::client::Registration;
+use crate::isupport::ISupport;
+use crate::message::GenericIrcCommand;
+use crate::message::GenericIrcCommandType;
+use crate::message::IrcCommand;
 use crate::message::IrcMessage;
+use crate::stats::LUserStats;
+use crate::users::User;
+use crate::users::WhoisInfo;
+use crate::users::WhowasEntry;
+use crate::users::WhoxEntry;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Event {
     RawMessage(IrcMessage),
+    // Every line exactly as received, line ending included, before it's parsed. Only
+    // emitted with ClientBuilder::with_raw_lines
+    RawLine(String),
 
     StatusChange,
     WelcomeMsg(String),
-    ErrorMsg(String),
-    Notice(String),
+    Registered(Registration),
+    // Everything advertised so far, sent once a run of 005 lines is over
+    ISupport(ISupport),
+    ErrorMsg {
+        from: Option<String>,
+        text: String,
+    },
+    // We were removed from the network by an operator or services, the server is about to
+    // close the connection. `by` is the nick or server name that sent the KILL
+    Killed {
+        by: Option<String>,
+        reason: String,
+    },
+    // PRIVMSG, except for CTCP ACTION which is an Event::Action. The message's other
+    // tags are in the Event::RawMessage dispatched right before
+    Message {
+        from: Option<String>,
+        target: String,
+        text: String,
+        // Only set with ClientBuilder::with_stripped_formatting
+        stripped_text: Option<String>,
+        msgid: Option<String>,
+        time: Option<SystemTime>,
+        // The msgid this message replies to, from the +draft/reply tag
+        reply_to: Option<String>,
+    },
+    Notice {
+        from: Option<String>,
+        target: String,
+        text: String,
+        // Only set with ClientBuilder::with_stripped_formatting
+        stripped_text: Option<String>,
+        msgid: Option<String>,
+        time: Option<SystemTime>,
+        kind: NoticeKind,
+    },
+    // CTCP ACTION, i.e. /me
+    Action {
+        from: Option<String>,
+        target: String,
+        text: String,
+        msgid: Option<String>,
+        time: Option<SystemTime>,
+    },
+    TagMsg {
+        from: Option<String>,
+        target: String,
+        tags: Vec<(String, Option<String>)>,
+    },
+    Joined {
+        channel: String,
+        nick: String,
+        // We joined the channel, as opposed to someone joining one we're in
+        is_self: bool,
+    },
+    Parted {
+        channel: String,
+        nick: String,
+        reason: Option<String>,
+        // We left the channel
+        is_self: bool,
+    },
+    Kicked {
+        channel: String,
+        nick: String,
+        by: Option<String>,
+        reason: Option<String>,
+        // We were the one kicked
+        is_self: bool,
+    },
+    Quit {
+        nick: String,
+        reason: Option<String>,
+        // Only seen on servers that echo our own QUIT back
+        is_self: bool,
+    },
+    NickChange {
+        old: String,
+        new: String,
+        // Our own nick changed, whether we asked for it or the server forced it
+        is_self: bool,
+    },
+    // A complete NAMES reply
+    Names {
+        channel: String,
+        members: Vec<Member>,
+    },
+    ChannelMode {
+        channel: String,
+        // Nick, or server name for modes set by the server
+        by: Option<String>,
+        changes: Vec<ModeChange>,
+    },
+    Topic {
+        channel: String,
+        topic: Option<String>,
+    },
+    // The channel's current modes, in reply to a MODE query. Replaces what was known
+    ChannelModeIs {
+        channel: String,
+        modes: HashMap<char, Option<String>>,
+    },
+    ChannelCreated {
+        channel: String,
+        created: SystemTime,
+    },
+    TopicInfo {
+        channel: String,
+        set_by: String,
+        set_at: SystemTime,
+    },
+    BanList {
+        channel: String,
+        entries: Vec<ListEntry>,
+    },
+    // Invite exceptions (+I), see Client::invite_list
+    InviteList {
+        channel: String,
+        entries: Vec<ListEntry>,
+    },
+    // `who` was invited to the channel. Invites for others are only seen with the
+    // invite-notify capability, in channels where we're an operator
+    Invited {
+        who: String,
+        by: Option<String>,
+        channel: String,
+        // We were the one invited
+        is_self: bool,
+    },
+    WhowasResult {
+        nick: String,
+        entries: Vec<WhowasEntry>,
+    },
+    // A complete WHOIS reply, not sent for nicks that don't exist
+    WhoisResult(WhoisInfo),
+    // Our current user modes in reply to MODE <nick>, e.g. Client::query_user_modes. Live
+    // changes arrive as UnhandledMessage
+    UserModeIs(HashSet<char>),
+    // One reply to Client::whox, `token` is the number it returned
+    WhoxResult {
+        token: u16,
+        entry: WhoxEntry,
+    },
+    AwayNotify {
+        nick: String,
+        message: Option<String>,
+    },
+    // A nick tracked with Client::track_nick came online or went offline, first sent with its
+    // state when tracking starts. From MONITOR or WATCH, whichever the server supports. `info`
+    // is what's known about an online user, see Client::user. Away and account changes of
+    // tracked nicks need the extended-monitor capability
+    PresenceChange {
+        nick: String,
+        online: bool,
+        info: Option<User>,
+    },
 
+    // Our host as others see it from now on, e.g. a cloak. From RPL_HOSTHIDDEN (396)
+    HostChanged(String),
+    // Needs the chghost capability
+    ChgHost {
+        nick: String,
+        user: String,
+        host: String,
+    },
+    // Needs the account-notify capability, None when logged out
+    AccountChange {
+        nick: String,
+        account: Option<String>,
+    },
+    // Needs the setname capability
+    RealnameChange {
+        nick: String,
+        realname: String,
+    },
     Motd,
 
+    ServerError {
+        code: u16,
+        params: Vec<String>,
+        message: String,
+    },
+
+    SaslSuccess(String),
+    SaslFailed(String),
+    // Everything the server advertised in reply to CAP LS with its value, once the last LS line
+    // is in. ClientBuilder::capability_negotiator picks what to request from the same list
+    CapabilitiesAvailable(Vec<(String, Option<String>)>),
+    // An enabled capability withdrawn by the server with CAP DEL
+    CapabilityLost(String),
+    // An incoming line longer than ClientBuilder::max_receive_length was skipped, with its
+    // length in bytes
+    LineTooLong(usize),
+
+    // Once per run of LUSERS replies, after the last one. See Client::luser_stats
+    LUserStats(LUserStats),
+    // We were marked away with the message, or back when it's None. Only emitted with
+    // ClientBuilder::auto_away
+    Away {
+        message: Option<String>,
+    },
+    // Only emitted with ClientBuilder::manual_ping_reply
+    Ping(String),
+    // The server answering a PING. `latency` is set when it echoes the token of the last
+    // PING we sent, the same round trip ConnectionStats::latency reports
+    Pong {
+        token: String,
+        latency: Option<Duration>,
+    },
     UnhandledMessage(IrcMessage), 
 }
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NoticeKind {
+    // From the server itself, e.g. "*** Looking up your hostname" while connecting
+    Server,
+    // From a user or services
+    User,
+}
+
+impl Event {
+    // Code, params and trailing of a numeric the library doesn't have a variant for
+    pub fn as_numeric(&self) -> Option<(u16, &[String], Option<&str>)> {
+        match self {
+            Event::UnhandledMessage(IrcMessage {
+                command: IrcCommand::Generic(GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(code),
+                    params,
+                    trailing,
+                }),
+                ..
+            }) => Some((*code, params.as_slice(), trailing.as_deref())),
+            _ => None,
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn as_numeric() {
+        let event = Event::UnhandledMessage(IrcMessage::try_from(":irc.example.net 276 Jimmy Bobby :has client certificate fingerprint abc\r\n").unwrap());
+        assert_eq!(event.as_numeric(), Some((276, &["Jimmy".to_string(), "Bobby".to_string()][..], Some("has client certificate fingerprint abc"))));
+
+        let event = Event::UnhandledMessage(IrcMessage::try_from(":Jimmy!jim@example.com PRIVMSG #rust :hello\r\n").unwrap());
+        assert_eq!(event.as_numeric(), None);
+
+        assert_eq!(Event::StatusChange.as_numeric(), None);
+    }
+}
@@ -0,0 +1,125 @@
+// Lines captured from real daemons, grouped by daemon so their quirks stay documented
+// next to the lines that show them. Every line here should parse into a typed variant
+
+pub mod inspircd {
+    pub const WELCOME: &str = ":irc.inspircd.example 001 Jimmy :Welcome to the InspIRCd IRC Network Jimmy!jimmy@127.0.0.1\r\n";
+    pub const YOUR_HOST: &str = ":irc.inspircd.example 002 Jimmy :Your host is irc.inspircd.example, running version InspIRCd-3\r\n";
+    pub const CREATED: &str = ":irc.inspircd.example 003 Jimmy :This server was created 10:12:44 Jan 02 2024\r\n";
+    // Also lists the modes that take a parameter
+    pub const MY_INFO: &str = ":irc.inspircd.example 004 Jimmy irc.inspircd.example InspIRCd-3 iosw biklmnopstv bklov\r\n";
+    pub const ISUPPORT: &str = ":irc.inspircd.example 005 Jimmy AWAYLEN=200 CASEMAPPING=rfc1459 CHANMODES=b,k,l,imnpst CHANTYPES=# :are supported by this server\r\n";
+    pub const LUSER_CLIENT: &str = ":irc.inspircd.example 251 Jimmy :There are 2 users and 0 invisible on 1 servers\r\n";
+    pub const LUSER_ME: &str = ":irc.inspircd.example 255 Jimmy :I have 2 clients and 0 servers\r\n";
+    pub const LOCAL_USERS: &str = ":irc.inspircd.example 265 Jimmy :Current local users: 2  Max: 2\r\n";
+    pub const GLOBAL_USERS: &str = ":irc.inspircd.example 266 Jimmy :Current global users: 2  Max: 2\r\n";
+    pub const MOTD_START: &str = ":irc.inspircd.example 375 Jimmy :irc.inspircd.example message of the day\r\n";
+    pub const MOTD: &str = ":irc.inspircd.example 372 Jimmy :- Welcome!\r\n";
+    pub const END_OF_MOTD: &str = ":irc.inspircd.example 376 Jimmy :End of message of the day.\r\n";
+    pub const HOST_HIDDEN: &str = ":irc.inspircd.example 396 Jimmy 1a2b3c4d.example :is now your displayed host\r\n";
+    pub const TOPIC_WHO_TIME: &str = ":irc.inspircd.example 333 Jimmy #rust Jimmy!jimmy@127.0.0.1 1704190364\r\n";
+
+    pub const ALL: &[&str] = &[
+        WELCOME,
+        YOUR_HOST,
+        CREATED,
+        MY_INFO,
+        ISUPPORT,
+        LUSER_CLIENT,
+        LUSER_ME,
+        LOCAL_USERS,
+        GLOBAL_USERS,
+        MOTD_START,
+        MOTD,
+        END_OF_MOTD,
+        HOST_HIDDEN,
+        TOPIC_WHO_TIME,
+    ];
+}
+
+pub mod solanum {
+    pub const WELCOME: &str = ":solanum.example 001 Jimmy :Welcome to the Example Internet Relay Chat Network Jimmy\r\n";
+    pub const MY_INFO: &str = ":solanum.example 004 Jimmy solanum.example solanum-1.0-dev DGIMQRSZaghilopsuwz CFILMPQRSTbcefgijklmnopqrstuvz bkloveqjfI\r\n";
+    pub const ISUPPORT: &str = ":solanum.example 005 Jimmy WHOX ETRACE FNC SAFELIST ELIST=CTU CALLERID=g MONITOR=100 :are supported by this server\r\n";
+    pub const LUSER_OP: &str = ":solanum.example 252 Jimmy 1 :IRC Operators online\r\n";
+    pub const LUSER_UNKNOWN: &str = ":solanum.example 253 Jimmy 1 :unknown connection(s)\r\n";
+    pub const LUSER_CHANNELS: &str = ":solanum.example 254 Jimmy 3 :channels formed\r\n";
+    // The counts are sent as parameters before the text
+    pub const LOCAL_USERS: &str = ":solanum.example 265 Jimmy 4 12 :Current local users 4, max 12\r\n";
+    pub const GLOBAL_USERS: &str = ":solanum.example 266 Jimmy 4 12 :Current global users 4, max 12\r\n";
+    pub const NO_TOPIC: &str = ":solanum.example 331 Jimmy #rust :No topic is set.\r\n";
+    pub const TOPIC: &str = ":solanum.example 332 Jimmy #rust :Rust programming | https://www.rust-lang.org\r\n";
+    pub const BAN_LIST: &str = ":solanum.example 367 Jimmy #rust *!*@spam.example Jimmy!jimmy@127.0.0.1 1704190364\r\n";
+    pub const END_OF_BAN_LIST: &str = ":solanum.example 368 Jimmy #rust :End of Channel Ban List\r\n";
+    pub const CHANOP_PRIVS_NEEDED: &str = ":solanum.example 482 Jimmy #rust :You're not a channel operator\r\n";
+    pub const SASL_SUCCESS: &str = ":solanum.example 903 Jimmy :SASL authentication successful\r\n";
+    pub const SASL_FAIL: &str = ":solanum.example 904 Jimmy :SASL authentication failed\r\n";
+
+    pub const ALL: &[&str] = &[
+        WELCOME,
+        MY_INFO,
+        ISUPPORT,
+        LUSER_OP,
+        LUSER_UNKNOWN,
+        LUSER_CHANNELS,
+        LOCAL_USERS,
+        GLOBAL_USERS,
+        NO_TOPIC,
+        TOPIC,
+        BAN_LIST,
+        END_OF_BAN_LIST,
+        CHANOP_PRIVS_NEEDED,
+        SASL_SUCCESS,
+        SASL_FAIL,
+    ];
+}
+
+pub mod unrealircd {
+    pub const WELCOME: &str = ":irc.unrealircd.example 001 Jimmy :Welcome to the UnrealIRCd IRC Network Jimmy!jimmy@127.0.0.1\r\n";
+    // No list of modes that take a parameter
+    pub const MY_INFO: &str = ":irc.unrealircd.example 004 Jimmy irc.unrealircd.example UnrealIRCd-6.1.4 iowrsxzdHtIDZRqpWGTSB lvhopsmntikraqbeIHzMQNRTOVKDdGLPZSCcf\r\n";
+    pub const ISUPPORT: &str = ":irc.unrealircd.example 005 Jimmy AWAYLEN=307 BOT=B CASEMAPPING=ascii CHANLIMIT=#:10 CHANMODES=beI,fkL,lFH,cdimnprstzCDGKMNOPQRSTVZ :are supported by this server\r\n";
+    pub const LOCAL_USERS: &str = ":irc.unrealircd.example 265 Jimmy 1 1 :Current local users 1, max 1\r\n";
+    pub const WHOWAS_USER: &str = ":irc.unrealircd.example 314 Jimmy Bobby bob 127.0.0.1 * :Bobby Tables\r\n";
+    pub const END_OF_WHOWAS: &str = ":irc.unrealircd.example 369 Jimmy Bobby :End of WHOWAS\r\n";
+    pub const WAS_NO_SUCH_NICK: &str = ":irc.unrealircd.example 406 Jimmy Robert :There was no such nickname\r\n";
+    pub const HOST_HIDDEN: &str = ":irc.unrealircd.example 396 Jimmy Clk-1A2B3C4D :is now your displayed host\r\n";
+
+    pub const ALL: &[&str] = &[
+        WELCOME,
+        MY_INFO,
+        ISUPPORT,
+        LOCAL_USERS,
+        WHOWAS_USER,
+        END_OF_WHOWAS,
+        WAS_NO_SUCH_NICK,
+        HOST_HIDDEN,
+    ];
+}
+
+pub mod ngircd {
+    pub const WELCOME: &str = ":irc.ngircd.example 001 Jimmy :Welcome to the Internet Relay Network Jimmy!~jimmy@localhost\r\n";
+    pub const YOUR_HOST: &str = ":irc.ngircd.example 002 Jimmy :Your host is irc.ngircd.example, running version ngircd-26.1 (x86_64/pc/linux-gnu)\r\n";
+    pub const MY_INFO: &str = ":irc.ngircd.example 004 Jimmy irc.ngircd.example ngircd-26.1 abBcCFiIoqrRswx abehiIklmMnoOPqQrRstvVz\r\n";
+    pub const ISUPPORT: &str = ":irc.ngircd.example 005 Jimmy RFC2812 IRCD=ngIRCd CHARSET=UTF-8 CASEMAPPING=ascii PREFIX=(qaohv)~&@%+ :are supported on this server\r\n";
+    pub const LUSER_CLIENT: &str = ":irc.ngircd.example 251 Jimmy :There are 1 users and 0 services on 1 servers\r\n";
+    pub const LUSER_ME: &str = ":irc.ngircd.example 255 Jimmy :I have 1 users, 0 services and 0 servers\r\n";
+    pub const LOCAL_USERS: &str = ":irc.ngircd.example 265 Jimmy 1 1 :Current local users: 1, Max: 1\r\n";
+    pub const GLOBAL_USERS: &str = ":irc.ngircd.example 266 Jimmy 1 1 :Current global users: 1, Max: 1\r\n";
+    pub const MOTD_START: &str = ":irc.ngircd.example 375 Jimmy :- irc.ngircd.example message of the day\r\n";
+    pub const MOTD: &str = ":irc.ngircd.example 372 Jimmy :- Hello!\r\n";
+    pub const END_OF_MOTD: &str = ":irc.ngircd.example 376 Jimmy :End of MOTD command\r\n";
+
+    pub const ALL: &[&str] = &[
+        WELCOME,
+        YOUR_HOST,
+        MY_INFO,
+        ISUPPORT,
+        LUSER_CLIENT,
+        LUSER_ME,
+        LOCAL_USERS,
+        GLOBAL_USERS,
+        MOTD_START,
+        MOTD,
+        END_OF_MOTD,
+    ];
+}
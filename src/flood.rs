@@ -0,0 +1,142 @@
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::message::IrcCommand;
+
+// How fast lines may be sent before the server would disconnect us for flooding.
+// Each line adds a penalty to a timer that otherwise follows the clock, and a line whose
+// penalty would take the timer more than the allowance ahead waits for it to catch up
+// (RFC 1459 section 8.10), so the Rfc preset sends 5 lines in a burst. The penalty per line:
+//
+//   Rfc        2s, up to 10s ahead
+//   Unreal     1s plus 1s for every full 120 bytes, up to 10s ahead
+//   InspIRCd   1s whatever the length, up to 5s ahead
+//   None       no limit, lines go out as fast as the socket takes them
+//
+// No ISUPPORT token describes these limits, so the preset should match the server's
+// daemon, see RPL_MYINFO (004)
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum FloodModel {
+    Rfc,
+    Unreal,
+    InspIRCd,
+    #[default]
+    None,
+}
+
+impl FloodModel {
+    // `length` is the whole line in bytes, including the CRLF
+    pub fn penalty(&self, length: usize) -> Duration {
+        match self {
+            FloodModel::Rfc => Duration::from_secs(2),
+            FloodModel::Unreal => Duration::from_secs(1 + (length / 120) as u64),
+            FloodModel::InspIRCd => Duration::from_secs(1),
+            FloodModel::None => Duration::ZERO,
+        }
+    }
+
+    // How far the timer may run ahead before sends have to wait
+    pub fn allowance(&self) -> Duration {
+        match self {
+            FloodModel::Rfc | FloodModel::Unreal => Duration::from_secs(10),
+            FloodModel::InspIRCd => Duration::from_secs(5),
+            FloodModel::None => Duration::MAX,
+        }
+    }
+}
+
+// High priority lines skip the wait for lines queued ahead of them, so a backlog of chat
+// can't delay a PONG until the server times us out. They still count towards the limit.
+// Lines of the same priority go out in the order they were sent
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum Priority {
+    High,
+    #[default]
+    Normal,
+}
+
+impl Priority {
+    // PING, PONG and QUIT are high priority, everything else normal
+    pub fn of(command: &IrcCommand) -> Self {
+        match command {
+            IrcCommand::Ping(_) | IrcCommand::Pong(_) | IrcCommand::Quit(_) => Priority::High,
+            _ => Priority::Normal,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct FloodTimer {
+    timer: Mutex<Option<Instant>>,
+}
+
+impl FloodTimer {
+    pub(crate) fn reset(&self) {
+        *self.timer.lock().unwrap() = None;
+    }
+
+    // Books a line of `length` bytes and returns how long to wait before sending it
+    pub(crate) fn reserve(&self, model: FloodModel, length: usize, priority: Priority, now: Instant) -> Duration {
+        if model == FloodModel::None {
+            return Duration::ZERO;
+        }
+
+        let mut timer = self.timer.lock().unwrap();
+        let end = timer.map_or(now, |m| m.max(now)) + model.penalty(length);
+        *timer = Some(end);
+
+        match priority {
+            Priority::High => Duration::ZERO,
+            Priority::Normal => (end - now).saturating_sub(model.allowance()),
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flood_timer() {
+        let timer = FloodTimer::default();
+        let now = Instant::now();
+
+        // 5 lines fill the 10s allowance, the 6th has to wait 2s and the 7th 2s after that
+        for _ in 0..5 {
+            assert_eq!(timer.reserve(FloodModel::Rfc, 20, Priority::Normal, now), Duration::ZERO);
+        }
+        assert_eq!(timer.reserve(FloodModel::Rfc, 20, Priority::Normal, now), Duration::from_secs(2));
+        assert_eq!(timer.reserve(FloodModel::Rfc, 20, Priority::Normal, now), Duration::from_secs(4));
+
+        // The timer catches up with the clock
+        assert_eq!(timer.reserve(FloodModel::Rfc, 20, Priority::Normal, now + Duration::from_secs(30)), Duration::ZERO);
+
+        timer.reset();
+        // 3s each, the 4th would take the timer to 12s
+        assert_eq!(timer.reserve(FloodModel::Unreal, 250, Priority::Normal, now), Duration::ZERO);
+        assert_eq!(timer.reserve(FloodModel::Unreal, 250, Priority::Normal, now), Duration::ZERO);
+        assert_eq!(timer.reserve(FloodModel::Unreal, 250, Priority::Normal, now), Duration::ZERO);
+        assert_eq!(timer.reserve(FloodModel::Unreal, 250, Priority::Normal, now), Duration::from_secs(2));
+
+        timer.reset();
+        for _ in 0..5 {
+            assert_eq!(timer.reserve(FloodModel::InspIRCd, 20, Priority::Normal, now), Duration::ZERO);
+        }
+        assert_eq!(timer.reserve(FloodModel::InspIRCd, 20, Priority::Normal, now), Duration::from_secs(1));
+
+        // A full timer doesn't hold back high priority lines, but they still count
+        timer.reset();
+        for _ in 0..6 {
+            timer.reserve(FloodModel::Rfc, 20, Priority::Normal, now);
+        }
+        assert_eq!(timer.reserve(FloodModel::Rfc, 20, Priority::High, now), Duration::ZERO);
+        assert_eq!(timer.reserve(FloodModel::Rfc, 20, Priority::Normal, now), Duration::from_secs(6));
+
+        for _ in 0..100 {
+            assert_eq!(timer.reserve(FloodModel::None, 512, Priority::Normal, now), Duration::ZERO);
+        }
+    }
+}
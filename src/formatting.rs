@@ -0,0 +1,321 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+const BOLD: char = '\x02';
+const COLOR: char = '\x03';
+const HEX_COLOR: char = '\x04';
+const RESET: char = '\x0F';
+const MONOSPACE: char = '\x11';
+const REVERSE: char = '\x16';
+const ITALIC: char = '\x1D';
+const STRIKETHROUGH: char = '\x1E';
+const UNDERLINE: char = '\x1F';
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Style {
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
+    pub monospace: bool,
+    pub reverse: bool,
+    // mIRC color codes, None is the client's default color
+    pub foreground: Option<u8>,
+    pub background: Option<u8>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Span {
+    pub text: String,
+    pub style: Style,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Color {
+    White,
+    Black,
+    Blue,
+    Green,
+    Red,
+    Brown,
+    Magenta,
+    Orange,
+    Yellow,
+    LightGreen,
+    Cyan,
+    LightCyan,
+    LightBlue,
+    Pink,
+    Grey,
+    LightGrey,
+}
+
+impl Color {
+    pub fn code(self) -> u8 {
+        self as u8
+    }
+}
+
+// Builds message text with formatting codes, every segment closes its own codes
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Formatted {
+    buffer: String,
+}
+
+impl Formatted {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text(mut self, text: &str) -> Self {
+        // A digit right after a closing color code would be read as a new color
+        if self.buffer.ends_with(COLOR) && text.starts_with(|c: char| c.is_ascii_digit()) {
+            self.buffer.push(BOLD);
+            self.buffer.push(BOLD);
+        }
+
+        self.buffer.push_str(text);
+        self
+    }
+
+    pub fn bold(self, text: &str) -> Self {
+        self.wrap(BOLD, text)
+    }
+
+    pub fn italic(self, text: &str) -> Self {
+        self.wrap(ITALIC, text)
+    }
+
+    pub fn underline(self, text: &str) -> Self {
+        self.wrap(UNDERLINE, text)
+    }
+
+    pub fn strikethrough(self, text: &str) -> Self {
+        self.wrap(STRIKETHROUGH, text)
+    }
+
+    pub fn color(mut self, foreground: Color, text: &str) -> Self {
+        self.buffer.push_str(&format!("{}{:02}", COLOR, foreground.code()));
+        self.push_colored(text)
+    }
+
+    pub fn colors(mut self, foreground: Color, background: Color, text: &str) -> Self {
+        self.buffer.push_str(&format!("{}{:02},{:02}", COLOR, foreground.code(), background.code()));
+        self.push_colored(text)
+    }
+
+    pub fn build(self) -> String {
+        self.buffer
+    }
+
+    fn wrap(mut self, code: char, text: &str) -> Self {
+        self.buffer.push(code);
+        self.buffer.push_str(text);
+        self.buffer.push(code);
+        self
+    }
+
+    fn push_colored(mut self, text: &str) -> Self {
+        // Codes are always two digits so text starting with a digit is safe, but a
+        // leading comma would be read as a background color without a separator
+        if text.starts_with(',') {
+            self.buffer.push(BOLD);
+            self.buffer.push(BOLD);
+        }
+
+        self.buffer.push_str(text);
+        self.buffer.push(COLOR);
+        self
+    }
+}
+
+pub fn strip_formatting(text: &str) -> String {
+    parse_formatting(text).into_iter().map(|m| m.text).collect()
+}
+
+pub fn parse_formatting(text: &str) -> Vec<Span> {
+    let mut spans = Vec::new();
+    let mut style = Style::default();
+    let mut buffer = String::new();
+
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if !matches!(c, BOLD | COLOR | HEX_COLOR | RESET | MONOSPACE | REVERSE | ITALIC | STRIKETHROUGH | UNDERLINE) {
+            buffer.push(c);
+            continue;
+        }
+
+        if !buffer.is_empty() {
+            spans.push(Span {
+                text: std::mem::take(&mut buffer),
+                style: style.clone(),
+            });
+        }
+
+        match c {
+            BOLD => style.bold = !style.bold,
+            ITALIC => style.italic = !style.italic,
+            UNDERLINE => style.underline = !style.underline,
+            STRIKETHROUGH => style.strikethrough = !style.strikethrough,
+            MONOSPACE => style.monospace = !style.monospace,
+            REVERSE => style.reverse = !style.reverse,
+            RESET => style = Style::default(),
+            COLOR => {
+                match take_digits(&mut chars, 2) {
+                    Some(foreground) => {
+                        style.foreground = color_code(&foreground);
+
+                        if let Some(background) = take_background(&mut chars, 2) {
+                            style.background = color_code(&background);
+                        }
+                    },
+                    // A lone color code resets both colors
+                    None => {
+                        style.foreground = None;
+                        style.background = None;
+                    },
+                }
+            },
+            // Hex colors have no mIRC code to map to, so they are only skipped
+            HEX_COLOR => {
+                if take_hex(&mut chars).is_some() {
+                    take_background_hex(&mut chars);
+                }
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    if !buffer.is_empty() {
+        spans.push(Span {
+            text: buffer,
+            style,
+        });
+    }
+
+    spans
+}
+
+fn color_code(digits: &str) -> Option<u8> {
+    match digits.parse::<u8>() {
+        Ok(99) | Err(_) => None,
+        Ok(code) => Some(code),
+    }
+}
+
+fn take_digits(chars: &mut Peekable<Chars>, max: usize) -> Option<String> {
+    let mut digits = String::new();
+
+    while digits.len() < max {
+        match chars.peek() {
+            Some(c) if c.is_ascii_digit() => digits.push(chars.next().unwrap()),
+            _ => break,
+        }
+    }
+
+    if digits.is_empty() {
+        None
+    } else {
+        Some(digits)
+    }
+}
+
+// The comma is only part of the color code when a color follows it
+fn take_background(chars: &mut Peekable<Chars>, max: usize) -> Option<String> {
+    let mut lookahead = chars.clone();
+
+    if lookahead.next() != Some(',') || !lookahead.peek().is_some_and(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    chars.next();
+    take_digits(chars, max)
+}
+
+fn take_hex(chars: &mut Peekable<Chars>) -> Option<String> {
+    let lookahead = chars.clone().take(6).collect::<String>();
+
+    if lookahead.len() == 6 && lookahead.chars().all(|c| c.is_ascii_hexdigit()) {
+        chars.nth(5);
+        Some(lookahead)
+    } else {
+        None
+    }
+}
+
+fn take_background_hex(chars: &mut Peekable<Chars>) {
+    let mut lookahead = chars.clone();
+
+    if lookahead.next() == Some(',') && take_hex(&mut lookahead).is_some() {
+        chars.nth(6);
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip() {
+        assert_eq!(strip_formatting("plain text"), "plain text".to_string());
+        assert_eq!(strip_formatting("\x02bold\x02 and \x1Dfancy\x0F"), "bold and fancy".to_string());
+        assert_eq!(strip_formatting("\x034red\x03 \x0304,12red on blue\x03"), "red red on blue".to_string());
+        // A comma not followed by a color is part of the text
+        assert_eq!(strip_formatting("\x034,text"), ",text".to_string());
+        assert_eq!(strip_formatting("\x03123"), "3".to_string());
+        assert_eq!(strip_formatting("\x04FF0000,00FF00hex"), "hex".to_string());
+    }
+
+    #[test]
+    fn build() {
+        assert_eq!(
+            Formatted::new().bold("hi").text(" ").color(Color::Red, "warn").build().as_bytes(),
+            b"\x02hi\x02 \x0304warn\x03",
+        );
+
+        assert_eq!(
+            Formatted::new().colors(Color::White, Color::Blue, "1st").underline("u").build().as_bytes(),
+            b"\x0300,021st\x03\x1Fu\x1F",
+        );
+
+        assert_eq!(
+            Formatted::new().color(Color::Green, ",5").build().as_bytes(),
+            b"\x0303\x02\x02,5\x03",
+        );
+
+        assert_eq!(strip_formatting(&Formatted::new().color(Color::Green, ",5").text("7").build()), ",57".to_string());
+    }
+
+    #[test]
+    fn spans() {
+        assert_eq!(parse_formatting("a\x02b\x0304,01c\x0Fd"), vec![
+            Span {
+                text: "a".to_string(),
+                style: Style::default(),
+            },
+            Span {
+                text: "b".to_string(),
+                style: Style {
+                    bold: true,
+                    ..Style::default()
+                },
+            },
+            Span {
+                text: "c".to_string(),
+                style: Style {
+                    bold: true,
+                    foreground: Some(4),
+                    background: Some(1),
+                    ..Style::default()
+                },
+            },
+            Span {
+                text: "d".to_string(),
+                style: Style::default(),
+            },
+        ]);
+    }
+}
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use crate::context::Context;
+use crate::event::Event;
+use crate::event_handler::EventHandler;
+use crate::message::IrcCommand;
+
+// Answers every PING with a PONG. Only useful together with ClientBuilder::manual_ping_reply,
+// otherwise the client already does this on its own
+#[derive(Debug, Default)]
+pub struct PingReplyHandler;
+
+impl EventHandler for PingReplyHandler {
+    fn on_event(&self, ctx: Arc<Context>, event: Event) {
+        if let Event::Ping(token) = event {
+            let _ = ctx.send(IrcCommand::Pong(token));
+        }
+    }
+}
+
+// Joins the channels once registration is complete, on every connect
+#[derive(Debug, Default)]
+pub struct AutoJoinHandler(pub Vec<String>);
+
+impl EventHandler for AutoJoinHandler {
+    fn on_event(&self, ctx: Arc<Context>, event: Event) {
+        if matches!(event, Event::Registered(_)) && !self.0.is_empty() {
+            let _ = ctx.send(IrcCommand::Join(self.0.join(","), None));
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+
+    use crate::client::Motd;
+    use crate::client::Registration;
+    use crate::context::ConnectionStatus;
+
+    use super::*;
+
+    fn context() -> (Arc<Context>, mpsc::UnboundedReceiver<IrcCommand>) {
+        let (outgoing, receiver) = mpsc::unbounded_channel();
+
+        let ctx = Context {
+            outgoing: Some(outgoing),
+            ..Context::new(ConnectionStatus::Connected, Motd::Empty)
+        };
+
+        (Arc::new(ctx), receiver)
+    }
+
+    #[test]
+    fn handlers() {
+        let (ctx, mut sent) = context();
+
+        PingReplyHandler.on_event(ctx.clone(), Event::Ping("irc.example.net".to_string()));
+        PingReplyHandler.on_event(ctx.clone(), Event::WelcomeMsg("Welcome".to_string()));
+        assert_eq!(sent.try_recv(), Ok(IrcCommand::Pong("irc.example.net".to_string())));
+        assert!(sent.try_recv().is_err());
+
+        let handler = AutoJoinHandler(vec!["#rust".to_string(), "#go".to_string()]);
+        handler.on_event(ctx.clone(), Event::WelcomeMsg("Welcome".to_string()));
+        handler.on_event(ctx.clone(), Event::Registered(Registration::default()));
+        assert_eq!(sent.try_recv(), Ok(IrcCommand::Join("#rust,#go".to_string(), None)));
+        assert!(sent.try_recv().is_err());
+
+        // Nothing to send on
+        assert!(Context::new(ConnectionStatus::Connected, Motd::Empty).send(IrcCommand::Quit(None)).is_err());
+    }
+}
@@ -0,0 +1,127 @@
+use std::fmt::Display;
+
+use crate::casemapping::irc_lower;
+use crate::casemapping::CaseMapping;
+
+// A "nick!user@host" source or mask. Missing parts are filled in with "*", the same
+// way servers expand short bans like "nick" or "*@host"
+#[derive(Debug, PartialEq, Clone)]
+pub struct Hostmask {
+    pub nick: String,
+    pub user: String,
+    pub host: String,
+}
+
+impl Hostmask {
+    pub fn new(nick: &str, user: &str, host: &str) -> Self {
+        Self {
+            nick: nick.to_string(),
+            user: user.to_string(),
+            host: host.to_string(),
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        let (rest, host) = match value.rsplit_once('@') {
+            Some((rest, host)) => (rest, host),
+            None => (value, "*"),
+        };
+
+        let (nick, user) = match rest.split_once('!') {
+            Some((nick, user)) => (nick, user),
+            // "*@host" has no nick part
+            None if value.contains('@') => ("*", rest),
+            None => (rest, "*"),
+        };
+
+        Self::new(nick, user, host)
+    }
+
+    // Whether this hostmask is covered by `mask`, nicks compare with the server's casemapping
+    pub fn matches(&self, casemapping: CaseMapping, mask: &str) -> bool {
+        let mask = Hostmask::parse(mask);
+
+        glob_matches(&irc_lower(casemapping, &mask.nick), &irc_lower(casemapping, &self.nick))
+            && glob_matches(&mask.user.to_ascii_lowercase(), &self.user.to_ascii_lowercase())
+            && glob_matches(&mask.host.to_ascii_lowercase(), &self.host.to_ascii_lowercase())
+    }
+}
+
+impl Display for Hostmask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}!{}@{}", self.nick, self.user, self.host)
+    }
+}
+
+// Uses RFC 1459 casemapping for the nick, see Hostmask::matches for other casemappings
+pub fn mask_matches(mask: &str, nick: &str, user: &str, host: &str) -> bool {
+    Hostmask::new(nick, user, host).matches(CaseMapping::default(), mask)
+}
+
+// "*" matches any run of characters, "?" exactly one
+fn glob_matches(pattern: &str, value: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let value = value.chars().collect::<Vec<_>>();
+
+    let (mut p, mut v) = (0, 0);
+    // Where to resume after the last "*" when the rest fails to match
+    let mut backtrack = None;
+
+    while v < value.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, v));
+                p += 1;
+            },
+            Some(c) if *c == '?' || *c == value[v] => {
+                p += 1;
+                v += 1;
+            },
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    v = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                },
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(Hostmask::parse("Jimmy!jim@example.com"), Hostmask::new("Jimmy", "jim", "example.com"));
+        assert_eq!(Hostmask::parse("Jimmy"), Hostmask::new("Jimmy", "*", "*"));
+        assert_eq!(Hostmask::parse("*@example.com"), Hostmask::new("*", "*", "example.com"));
+        assert_eq!(Hostmask::parse("Jimmy!jim"), Hostmask::new("Jimmy", "jim", "*"));
+        assert_eq!(Hostmask::new("Jimmy", "jim", "example.com").to_string(), "Jimmy!jim@example.com".to_string());
+    }
+
+    #[test]
+    fn matching() {
+        assert!(mask_matches("*!*@*", "Jimmy", "jim", "example.com"));
+        assert!(mask_matches("*!*@*.example.com", "Jimmy", "jim", "host.example.com"));
+        assert!(!mask_matches("*!*@*.example.com", "Jimmy", "jim", "example.com"));
+        assert!(mask_matches("Jimmy!*@*", "jimmy", "jim", "example.com"));
+        assert!(mask_matches("*!~jim@*", "Jimmy", "~jim", "example.com"));
+        assert!(mask_matches("J?mmy!*@*", "Jimmy", "jim", "example.com"));
+        assert!(!mask_matches("J?mmy!*@*", "Jmmy", "jim", "example.com"));
+        assert!(mask_matches("*@192.168.*", "Jimmy", "jim", "192.168.0.1"));
+        assert!(mask_matches("Jimmy", "Jimmy", "jim", "example.com"));
+        assert!(mask_matches("*!*@*a*b*c", "Jimmy", "jim", "aXbXbXc"));
+
+        // Casemapping only applies to the nick
+        assert!(mask_matches("Jimmy[m]!*@*", "jimmy{m}", "jim", "example.com"));
+        assert!(!Hostmask::new("jimmy{m}", "jim", "example.com").matches(CaseMapping::Ascii, "Jimmy[m]!*@*"));
+        assert!(!mask_matches("*!jim[a]@*", "Jimmy", "jim{a}", "example.com"));
+    }
+}
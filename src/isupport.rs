@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+// Features the server advertised with RPL_ISUPPORT (005), merged across every 005 line.
+// Tokens without a value map to None
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ISupport {
+    tokens: HashMap<String, Option<String>>,
+}
+
+impl ISupport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Applies a single "KEY", "KEY=value" or "-KEY" token, the last removes an earlier KEY
+    pub fn apply(&mut self, token: &str) {
+        if let Some(key) = token.strip_prefix('-') {
+            self.tokens.remove(key);
+            return;
+        }
+
+        match token.split_once('=') {
+            // "KEY=" is the same as "KEY"
+            Some((key, "")) => self.tokens.insert(key.to_string(), None),
+            Some((key, value)) => self.tokens.insert(key.to_string(), Some(unescape(value))),
+            None => self.tokens.insert(token.to_string(), None),
+        };
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.tokens.contains_key(key)
+    }
+
+    // None for tokens that weren't advertised or have no value, see ISupport::contains
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.tokens.get(key)?.as_deref()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Option<String>)> {
+        self.tokens.iter()
+    }
+}
+
+// Values escape characters that can't appear in a parameter as "\xHH"
+fn unescape(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escaped = match bytes.get(i..i + 4) {
+            Some([b'\\', b'x', hex @ ..]) => std::str::from_utf8(hex).ok().and_then(|m| u8::from_str_radix(m, 16).ok()),
+            _ => None,
+        };
+
+        match escaped {
+            Some(byte) => {
+                unescaped.push(byte);
+                i += 4;
+            },
+            None => {
+                unescaped.push(bytes[i]);
+                i += 1;
+            },
+        }
+    }
+
+    String::from_utf8_lossy(&unescaped).into_owned()
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens() {
+        let mut isupport = ISupport::new();
+
+        for token in ["CHANTYPES=#&", "EXCEPTS", "NETWORK=Example\\x20Net", "SAFELIST", "TOPICLEN=", "-EXCEPTS"] {
+            isupport.apply(token);
+        }
+
+        assert_eq!(isupport.get("CHANTYPES"), Some("#&"));
+        assert_eq!(isupport.get("NETWORK"), Some("Example Net"));
+        assert!(isupport.contains("SAFELIST"));
+        assert_eq!(isupport.get("SAFELIST"), None);
+        assert!(isupport.contains("TOPICLEN"));
+        assert!(!isupport.contains("EXCEPTS"));
+        assert!(!isupport.contains("KICKLEN"));
+    }
+}
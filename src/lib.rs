@@ -4,3 +4,21 @@ pub mod client;
 pub mod event_handler;
 pub mod event;
 pub mod context;
+pub mod router;
+pub mod dispatch;
+pub mod formatting;
+pub mod channel;
+pub mod users;
+pub mod network;
+pub mod casemapping;
+pub mod ctcp;
+pub mod stats;
+pub mod hostmask;
+pub mod isupport;
+pub mod address;
+pub mod handlers;
+pub mod numeric;
+pub mod flood;
+
+#[cfg(test)]
+mod fixtures;
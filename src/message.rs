@@ -1,3 +1,5 @@
+use std::time::Duration;
+use std::time::SystemTime;
 use std::vec;
 
 use regex::Regex;
@@ -6,6 +8,8 @@ use crate::error::Error;
 
 
 
+const CAP_SUBCOMMANDS: [&str; 8] = ["LS", "LIST", "REQ", "ACK", "NAK", "END", "NEW", "DEL"];
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct IrcMessage {
     pub tags: Vec<(String, Option<String>)>,
@@ -13,6 +17,78 @@ pub struct IrcMessage {
     pub command: IrcCommand,
 }
 
+impl IrcMessage {
+    pub fn numeric(&self) -> Option<u16> {
+        match GenericIrcCommand::from(self.command.clone()).command {
+            GenericIrcCommandType::Number(number) => Some(number),
+            GenericIrcCommandType::Text(_) => None,
+        }
+    }
+
+    // None for messages without a prefix or sent by a server
+    pub fn sender_nick(&self) -> Option<&str> {
+        let prefix = self.prefix.as_deref()?;
+
+        match prefix.find(['!', '@']) {
+            Some(index) => Some(&prefix[..index]),
+            None if prefix.contains('.') => None,
+            None => Some(prefix),
+        }
+    }
+
+    // The inner option is None for tags without a value
+    pub fn get_tag(&self, key: &str) -> Option<Option<&str>> {
+        self.tags.iter()
+            .find(|(tag, _)| tag == key)
+            .map(|(_, value)| value.as_deref())
+    }
+
+    // Needs the message-tags capability
+    pub fn msgid(&self) -> Option<&str> {
+        self.get_tag("msgid").flatten()
+    }
+
+    // When the server received the message, needs the server-time capability
+    pub fn server_time(&self) -> Option<SystemTime> {
+        parse_server_time(self.get_tag("time").flatten()?)
+    }
+}
+
+// Parses the "YYYY-MM-DDThh:mm:ss.sssZ" timestamps of the server-time capability
+fn parse_server_time(value: &str) -> Option<SystemTime> {
+    let value = value.strip_suffix('Z')?;
+    let (date, time) = value.split_once('T')?;
+
+    let mut date = date.splitn(3, '-').map(|m| m.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+
+    let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+    let mut time = time.splitn(3, ':').map(|m| m.parse::<u64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    // Days since the epoch from a civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = u64::try_from(era * 146097 + day_of_era - 719468).ok()?;
+
+    let nanos = match fraction {
+        "" => 0,
+        fraction if fraction.len() <= 9 && fraction.chars().all(|c| c.is_ascii_digit()) => {
+            format!("{:0<9}", fraction).parse::<u32>().ok()?
+        },
+        _ => return None,
+    };
+
+    Some(SystemTime::UNIX_EPOCH + Duration::new(days * 86400 + hour * 3600 + minute * 60 + second, nanos))
+}
+
 impl TryFrom<&str> for IrcMessage {
     type Error = Error;
 
@@ -26,8 +102,8 @@ impl TryFrom<&str> for IrcMessage {
         let tags = match caps.get(1).map(|m| m.as_str().to_string()) {
             None => vec![],
             Some(tags) => {
-                tags.split(';').into_iter().map(|m| {
-                    match m.split_once("=") {
+                tags.split(';').map(|m| {
+                    match m.split_once('=') {
                         Some((key, value)) => {
                             (key.to_string(), Some(value.to_string()))
                         },
@@ -45,8 +121,11 @@ impl TryFrom<&str> for IrcMessage {
             return Err(Error::NoCommand(value.to_string()));
         };
 
-        let Ok(command) = IrcCommand::try_from(command) else {
-            return Err(Error::Invalid);
+        // Keep number parsing errors so callers can tell them apart from malformed lines
+        let command = match IrcCommand::try_from(command) {
+            Ok(command) => command,
+            Err(Error::ParseInt(value)) => return Err(Error::ParseInt(value)),
+            Err(_) => return Err(Error::Invalid),
         };
 
         Ok(IrcMessage {
@@ -64,7 +143,7 @@ impl TryFrom<IrcMessage> for String {
         let mut buffer = String::new();
 
         if !value.tags.is_empty() {
-            buffer.push_str("@");
+            buffer.push('@');
 
             let length = value.tags.len();
 
@@ -75,12 +154,12 @@ impl TryFrom<IrcMessage> for String {
                     buffer.push_str(tag.0.as_str());
                 }
                 
-                if !(index == length - 1) {
-                    buffer.push_str(";");
+                if index != length - 1 {
+                    buffer.push(';');
                 }
             }
 
-            buffer.push_str(" ");
+            buffer.push(' ');
         }
 
         if let Some(prefix) = value.prefix {
@@ -101,13 +180,75 @@ impl TryFrom<IrcMessage> for String {
 pub enum IrcCommand {
     Pass(String),
     Nick(String),
-    // username, realname
-    User(String, String),
+    // username, mode bits (8 for invisible, 4 for wallops), realname
+    User(String, u8, String),
     Ping(String),
     Pong(String),
     Notice(String, String),
     // had to add Msg to stop compiler from complaining
     ErrorMsg(String),
+    Cap {
+        // Only present on replies from the server, "*" before registration
+        client: Option<String>,
+        subcommand: String,
+        params: Vec<String>,
+        capabilities: Vec<String>,
+    },
+    Authenticate(String),
+    // None when the user is no longer away
+    Away(Option<String>),
+    // The payload is carried in the message tags
+    TagMsg(String),
+    // channel, topic (None queries the topic, empty clears it)
+    Topic(String, Option<String>),
+    // target, mode string and arguments
+    Mode(String, Vec<String>),
+    // nick, maximum number of entries
+    Whowas(String, Option<u32>),
+    // New realname, needs the setname capability
+    SetName(String),
+    // New username and host, needs the chghost capability
+    ChgHost(String, String),
+    // Account name, "*" when logged out. Needs the account-notify capability
+    Account(String),
+    // Comma separated channels, "0" parts every channel, and comma separated keys paired
+    // with the channels by position. An empty key is a channel without one
+    Join(String, Option<String>),
+    // Comma separated channels, reason
+    Part(String, Option<String>),
+    // channel, nick, reason
+    Kick(String, String, Option<String>),
+    Quit(Option<String>),
+    // nick, reason. Servers put the path of the kill in front of the reason
+    Kill(String, String),
+    // nick, channel
+    Invite(String, String),
+    // target, text
+    Privmsg(String, String),
+    // Comma separated channels, None asks for every visible channel
+    Names(Option<String>),
+    // Comma separated channels, None lists every channel
+    List(Option<String>),
+    // mask, then "o" for operators only or a WHOX field list
+    Who(String, Option<String>),
+    // Comma separated nicks, server to ask. Passing the nick as the server gets the idle time
+    // from the user's own server. Sent as WHOIS [server] nicks
+    Whois(String, Option<String>),
+    // name, password
+    Oper(String, String),
+    Wallops(String),
+    Ison(Vec<String>),
+    // "+nick" and "-nick" entries to add and remove, or "C", "L" and "S" to clear, list and
+    // show the watch list. For servers advertising WATCH in ISUPPORT
+    Watch(Vec<String>),
+    // "+" or "-" with comma separated targets, or "C", "L" and "S" without. For servers
+    // advertising MONITOR in ISUPPORT
+    Monitor(String, Option<String>),
+    // Up to five nicks
+    Userhost(Vec<String>),
+    // Reference tag, "+" in front when the batch starts and "-" when it ends, then the batch
+    // type and its parameters on the start
+    Batch(String, Vec<String>),
 
     RplWelcome(String, String), // 001 RPL_WELCOME
     RplYourHost(String, String), // 002 RPL_YOURHOST
@@ -123,22 +264,119 @@ pub enum IrcCommand {
     // TODO: Add struct for caps
     RplISupport(String, Vec<String>, String), // 005 RPL_ISUPPORT
 
+    RplUModeIs(String, String), // 221 RPL_UMODEIS
     RplLUserClient(String, String), // 251 RPL_LUSERCLIENT
-    RplLUserOp(String, u32, String), // 252 RPL_LUSEROPS
-    RplLUserUnknown(String, u32, String), // 253 RPL_LUSERUNKNOWN
-    RplLUserChannels(String, u32, String), // 254 RPL_LUSERCHANNELS
+    RplLUserOp(String, u64, String), // 252 RPL_LUSEROPS
+    RplLUserUnknown(String, u64, String), // 253 RPL_LUSERUNKNOWN
+    RplLUserChannels(String, u64, String), // 254 RPL_LUSERCHANNELS
     RplLUserMe(String, String), // 255 RPL_LUSERME
 
-    RplLocalUsers(String, Option<(u32, u32)>, String), // 265 RPL_LOCALUSERS
-    RplGlobalUsers(String, Option<(u32, u32)>, String), // 266 RPL_GLOBALUSERS
+    RplLocalUsers(String, Option<(u64, u64)>, String), // 265 RPL_LOCALUSERS
+    RplGlobalUsers(String, Option<(u64, u64)>, String), // 266 RPL_GLOBALUSERS
+
+    RplWhoisUser {
+        client: String,
+        nick: String,
+        username: String,
+        host: String,
+        realname: String,
+    }, // 311 RPL_WHOISUSER
+    // client, nick, server, server info
+    RplWhoisServer(String, String, String, String), // 312 RPL_WHOISSERVER
+    RplWhoisOperator(String, String, String), // 313 RPL_WHOISOPERATOR
+    RplWhowasUser {
+        client: String,
+        nick: String,
+        username: String,
+        host: String,
+        realname: String,
+    }, // 314 RPL_WHOWASUSER
+    RplWhoisIdle {
+        client: String,
+        nick: String,
+        // Seconds
+        idle: u64,
+        // Unix timestamp, left out by some servers
+        signon: Option<u64>,
+        message: String,
+    }, // 317 RPL_WHOISIDLE
+    RplEndOfWhois(String, String, String), // 318 RPL_ENDOFWHOIS
+    // client, nick, channels with their status prefixes
+    RplWhoisChannels(String, String, Vec<String>), // 319 RPL_WHOISCHANNELS
+    // client, nick, account, message
+    RplWhoisAccount(String, String, String, String), // 330 RPL_WHOISACCOUNT
+
+    RplChannelModeIs {
+        client: String,
+        channel: String,
+        // e.g. "+ntk", parameters of the modes that take one follow in order
+        modes: String,
+        params: Vec<String>,
+    }, // 324 RPL_CHANNELMODEIS
+    // client, channel, unix timestamp
+    RplCreationTime(String, String, u64), // 329 RPL_CREATIONTIME
+
+    RplNoTopic(String, String, String), // 331 RPL_NOTOPIC
+    RplTopic(String, String, String), // 332 RPL_TOPIC
+    // client, channel, setter, unix timestamp
+    RplTopicWhoTime(String, String, String, u64), // 333 RPL_TOPICWHOTIME
+
+    RplBanList {
+        client: String,
+        channel: String,
+        mask: String,
+        set_by: Option<String>,
+        set_at: Option<u64>,
+    }, // 367 RPL_BANLIST
+    RplEndOfBanList(String, String, String), // 368 RPL_ENDOFBANLIST
+    // Invite exceptions (+I), same layout as RPL_BANLIST
+    RplInviteList {
+        client: String,
+        channel: String,
+        mask: String,
+        set_by: Option<String>,
+        set_at: Option<u64>,
+    }, // 346 RPL_INVITELIST
+    RplEndOfInviteList(String, String, String), // 347 RPL_ENDOFINVITELIST
+    RplEndOfWhowas(String, String, String), // 369 RPL_ENDOFWHOWAS
 
     RplMotdStart(String, String), // 375 RPL_MOTDSTART
     RplMotd(String, String), // 372 RPL_MOTD
     RplEndOfMotd(String, String), // 376 RPL_ENDOFMOTD
 
-    // TODO: Figure out what this is
+    RplNamReply {
+        client: String,
+        // "=" public, "*" private or "@" secret
+        symbol: String,
+        channel: String,
+        // Nicks with their status prefixes, e.g. "@Jimmy"
+        names: Vec<String>,
+    }, // 353 RPL_NAMREPLY
+    RplEndOfNames(String, String, String), // 366 RPL_ENDOFNAMES
+    // client, then the fields a WHOX query asked for in the order the server sends them.
+    // Their meaning depends on the query, see Client::whox
+    RplWhoSpcRpl(String, Vec<String>), // 354 RPL_WHOSPCRPL
+    // client, the host others see from now on, message
     RplHostHidden(String, String, String), // 396 RPL_HOSTHIDDEN
 
+    // client, nick, username, host, unix timestamp, message
+    RplLogOn(String, String, String, String, u64, String), // 600 RPL_LOGON
+    // Same layout as RPL_LOGON for the rest of the WATCH replies
+    RplLogOff(String, String, String, String, u64, String), // 601 RPL_LOGOFF
+    RplNowOn(String, String, String, String, u64, String), // 604 RPL_NOWON
+    // Username and host are "*" for nicks that aren't online
+    RplNowOff(String, String, String, String, u64, String), // 605 RPL_NOWOFF
+    // client, "nick!user@host" targets. Some servers send only the nick
+    RplMonOnline(String, Vec<String>), // 730 RPL_MONONLINE
+    // client, nicks
+    RplMonOffline(String, Vec<String>), // 731 RPL_MONOFFLINE
+
+    ErrWasNoSuchNick(String, String, String), // 406 ERR_WASNOSUCHNICK
+    ErrChanOPrivsNeeded(String, String, String), // 482 ERR_CHANOPRIVSNEEDED
+
+    RplSaslSuccess(String, String), // 903 RPL_SASLSUCCESS
+    ErrSaslFail(String, String), // 904 ERR_SASLFAIL
+
     Generic(GenericIrcCommand),
 }
 
@@ -149,14 +387,127 @@ impl TryFrom<GenericIrcCommand> for IrcCommand {
         match &value.command {
             GenericIrcCommandType::Text(command) => {
                 match command.as_str() {
-                    "PASS" => Ok(Self::Pass(value.params.get(0).unwrap().clone())),
-                    "NICK" => Ok(Self::Nick(value.params.get(0).unwrap().clone())),
-                    "USER" => Ok(Self::User(value.params.get(0).unwrap().clone(),
-                        value.params.get(1).unwrap().clone())),
-                    "PING" => Ok(Self::Ping(value.trailing.unwrap())),
-                    "PONG" => Ok(Self::Pong(value.trailing.unwrap())),
-                    "NOTICE" => Ok(Self::Notice(value.params.get(0).unwrap().clone(), value.trailing.unwrap())),
-                    "ERROR" => Ok(Self::ErrorMsg(value.trailing.unwrap())),
+                    "PASS" => Ok(Self::Pass(value.params.first().cloned().ok_or(Error::Invalid)?)),
+                    "NICK" => Ok(Self::Nick(value.params.first().cloned().ok_or(Error::Invalid)?)),
+                    "USER" => match (value.params.get(1), value.params.get(3).or(value.trailing.as_ref())) {
+                        (Some(mode), Some(realname)) if value.params.len() >= 3 => Ok(Self::User(
+                            value.params.first().cloned().ok_or(Error::Invalid)?,
+                            mode.parse::<u8>().unwrap_or(0),
+                            realname.clone(),
+                        )),
+                        (Some(realname), _) => Ok(Self::User(value.params.first().cloned().ok_or(Error::Invalid)?, 0, realname.clone())),
+                        (None, _) => Err(Error::Invalid),
+                    },
+                    // The token is usually the trailing, but some servers send it as a middle
+                    // param or leave it out entirely
+                    "PING" => Ok(Self::Ping(value.trailing.or(value.params.last().cloned()).unwrap_or_default())),
+                    "PONG" => Ok(Self::Pong(value.trailing.or(value.params.last().cloned()).unwrap_or_default())),
+                    "NOTICE" => Ok(Self::Notice(
+                        value.params.first().cloned().ok_or(Error::Invalid)?,
+                        value.trailing.or(value.params.get(1).cloned()).ok_or(Error::Invalid)?,
+                    )),
+                    "ERROR" => Ok(Self::ErrorMsg(value.trailing.ok_or(Error::Invalid)?)),
+                    "CAP" => {
+                        let mut params = value.params;
+
+                        let client = match params.first() {
+                            Some(param) if !CAP_SUBCOMMANDS.contains(&param.as_str()) => Some(params.remove(0)),
+                            _ => None,
+                        };
+
+                        if params.is_empty() {
+                            return Err(Error::Invalid);
+                        }
+
+                        let subcommand = params.remove(0);
+
+                        // Servers may send a single capability as a middle parameter instead of trailing
+                        let capabilities = match value.trailing {
+                            Some(trailing) => Some(trailing),
+                            None if client.is_some() || subcommand == "REQ" => params.pop(),
+                            None => None,
+                        }.map(|m| m.split_whitespace().map(|m| m.to_string()).collect()).unwrap_or_default();
+
+                        Ok(Self::Cap {
+                            client,
+                            subcommand,
+                            params,
+                            capabilities,
+                        })
+                    },
+                    "AWAY" => Ok(Self::Away(value.trailing)),
+                    // Some servers send the channel as trailing
+                    "JOIN" => {
+                        let mut args = value.params.into_iter().chain(value.trailing);
+                        Ok(Self::Join(args.next().ok_or(Error::Invalid)?, args.next()))
+                    },
+                    "PART" => Ok(Self::Part(value.params.first().cloned().ok_or(Error::Invalid)?, value.trailing)),
+                    "KICK" => Ok(Self::Kick(
+                        value.params.first().cloned().ok_or(Error::Invalid)?,
+                        value.params.get(1).cloned().ok_or(Error::Invalid)?,
+                        value.trailing,
+                    )),
+                    "QUIT" => Ok(Self::Quit(value.trailing)),
+                    // Some servers send the channel as trailing
+                    "INVITE" => Ok(Self::Invite(
+                        value.params.first().cloned().ok_or(Error::Invalid)?,
+                        value.params.get(1).cloned().or(value.trailing).ok_or(Error::Invalid)?,
+                    )),
+                    "KILL" => Ok(Self::Kill(
+                        value.params.first().cloned().ok_or(Error::Invalid)?,
+                        value.trailing.or(value.params.get(1).cloned()).unwrap_or_default(),
+                    )),
+                    "PRIVMSG" => Ok(Self::Privmsg(
+                        value.params.first().cloned().ok_or(Error::Invalid)?,
+                        value.trailing.or(value.params.get(1).cloned()).ok_or(Error::Invalid)?,
+                    )),
+                    "NAMES" => Ok(Self::Names(value.params.first().cloned())),
+                    "LIST" => Ok(Self::List(value.params.first().cloned())),
+                    "WHO" => Ok(Self::Who(value.params.first().cloned().ok_or(Error::Invalid)?, value.params.get(1).cloned())),
+                    "WHOIS" => match value.params.as_slice() {
+                        [nicks] => Ok(Self::Whois(nicks.clone(), None)),
+                        [server, nicks, ..] => Ok(Self::Whois(nicks.clone(), Some(server.clone()))),
+                        [] => Err(Error::Invalid),
+                    },
+                    "OPER" => Ok(Self::Oper(
+                        value.params.first().cloned().ok_or(Error::Invalid)?,
+                        value.params.get(1).cloned().or(value.trailing).ok_or(Error::Invalid)?,
+                    )),
+                    "WALLOPS" => Ok(Self::Wallops(value.trailing.or(value.params.first().cloned()).ok_or(Error::Invalid)?)),
+                    // Some clients send the nicks as one trailing
+                    "ISON" => Ok(Self::Ison(value.params.iter().map(|m| m.as_str()).chain(value.trailing.iter().flat_map(|m| m.split_whitespace())).map(|m| m.to_string()).collect())),
+                    "USERHOST" => Ok(Self::Userhost(value.params.iter().map(|m| m.as_str()).chain(value.trailing.iter().flat_map(|m| m.split_whitespace())).map(|m| m.to_string()).collect())),
+                    "WATCH" => Ok(Self::Watch(value.params.iter().map(|m| m.as_str()).chain(value.trailing.iter().flat_map(|m| m.split_whitespace())).map(|m| m.to_string()).collect())),
+                    "MONITOR" => Ok(Self::Monitor(value.params.first().cloned().ok_or(Error::Invalid)?, value.params.get(1).cloned().or(value.trailing))),
+                    "BATCH" => {
+                        let mut params = value.params.into_iter();
+                        let reference = params.next().ok_or(Error::Invalid)?;
+
+                        Ok(Self::Batch(reference, params.chain(value.trailing).collect()))
+                    },
+                    "CHGHOST" => Ok(Self::ChgHost(
+                        value.params.first().cloned().ok_or(Error::Invalid)?,
+                        value.params.get(1).cloned().or(value.trailing).ok_or(Error::Invalid)?,
+                    )),
+                    "ACCOUNT" => Ok(Self::Account(value.params.first().cloned().or(value.trailing).ok_or(Error::Invalid)?)),
+                    "SETNAME" => Ok(Self::SetName(value.trailing.or(value.params.first().cloned()).ok_or(Error::Invalid)?)),
+                    "TAGMSG" => Ok(Self::TagMsg(value.params.first().cloned().ok_or(Error::Invalid)?)),
+                    "TOPIC" => Ok(Self::Topic(value.params.first().cloned().ok_or(Error::Invalid)?, value.trailing)),
+                    "WHOWAS" => Ok(Self::Whowas(
+                        value.params.first().cloned().ok_or(Error::Invalid)?,
+                        match value.params.get(1) {
+                            Some(count) => Some(count.parse::<u32>().map_err(|_| Error::Invalid)?),
+                            None => None,
+                        },
+                    )),
+                    "MODE" => {
+                        let mut params = value.params.into_iter();
+                        let target = params.next().ok_or(Error::Invalid)?;
+
+                        // Some servers send the last argument as trailing
+                        Ok(Self::Mode(target, params.chain(value.trailing).collect()))
+                    },
+                    "AUTHENTICATE" => Ok(Self::Authenticate(value.params.first().cloned().or(value.trailing).ok_or(Error::Invalid)?)),
                     _ => {
                         #[cfg(debug_assertions)]
                         {
@@ -169,46 +520,184 @@ impl TryFrom<GenericIrcCommand> for IrcCommand {
             },
             GenericIrcCommandType::Number(command) => {
                 match command {
-                    001 => Ok(Self::RplWelcome(value.params.get(0).unwrap().clone(), value.trailing.unwrap())),
-                    002 => Ok(Self::RplYourHost(value.params.get(0).unwrap().clone(), value.trailing.unwrap())),
-                    003 => Ok(Self::RplCreated(value.params.get(0).unwrap().clone(), value.trailing.unwrap())),
-                    004 => Ok(Self::RplMyInfo{
-                        client: value.params.get(0).unwrap().clone(),
-                        server_name: value.params.get(1).unwrap().clone(),
-                        server_version: value.params.get(2).unwrap().clone(),
+                    1 => Ok(Self::RplWelcome(value.params.first().cloned().ok_or(Error::Invalid)?, value.trailing.ok_or(Error::Invalid)?)),
+                    2 => Ok(Self::RplYourHost(value.params.first().cloned().ok_or(Error::Invalid)?, value.trailing.ok_or(Error::Invalid)?)),
+                    3 => Ok(Self::RplCreated(value.params.first().cloned().ok_or(Error::Invalid)?, value.trailing.ok_or(Error::Invalid)?)),
+                    4 => Ok(Self::RplMyInfo{
+                        client: value.params.first().cloned().ok_or(Error::Invalid)?,
+                        server_name: value.params.get(1).cloned().ok_or(Error::Invalid)?,
+                        server_version: value.params.get(2).cloned().ok_or(Error::Invalid)?,
                         // TODO: Parse umodes and cmodes with their own struct
-                        umodes: value.params.get(3).unwrap().clone(),
-                        cmodes: value.params.get(4).unwrap().clone(),
-                        cmodes_params: value.params.get(5).map(|m| m.clone()),
+                        umodes: value.params.get(3).cloned().ok_or(Error::Invalid)?,
+                        cmodes: value.params.get(4).cloned().ok_or(Error::Invalid)?,
+                        cmodes_params: value.params.get(5).cloned(),
                     }),
-                    005 => Ok(Self::RplISupport(value.params.get(0).unwrap().clone(), value.params.into_iter().skip(1).collect(), value.trailing.unwrap())),
-                    251 => Ok(Self::RplLUserClient(value.params.get(0).unwrap().clone(), value.trailing.unwrap())),
-                    252 => Ok(Self::RplLUserOp(value.params.get(0).unwrap().clone(), value.params.get(1).unwrap().parse::<u32>().unwrap(), value.trailing.unwrap())),
-                    253 => Ok(Self::RplLUserUnknown(value.params.get(0).unwrap().clone(), value.params.get(1).unwrap().parse::<u32>().unwrap(), value.trailing.unwrap())),
-                    254 => Ok(Self::RplLUserChannels(value.params.get(0).unwrap().clone(), value.params.get(1).unwrap().parse::<u32>().unwrap(), value.trailing.unwrap())),
-                    255 => Ok(Self::RplLUserMe(value.params.get(0).unwrap().clone(), value.trailing.unwrap())),
+                    5 => Ok(Self::RplISupport(value.params.first().cloned().ok_or(Error::Invalid)?, value.params.into_iter().skip(1).collect(), value.trailing.ok_or(Error::Invalid)?)),
+                    // Some servers send the modes as the trailing parameter
+                    221 => Ok(Self::RplUModeIs(value.params.first().cloned().ok_or(Error::Invalid)?, value.params.get(1).cloned().or(value.trailing).ok_or(Error::Invalid)?)),
+                    251 => Ok(Self::RplLUserClient(value.params.first().cloned().ok_or(Error::Invalid)?, value.trailing.ok_or(Error::Invalid)?)),
+                    252 => Ok(Self::RplLUserOp(value.params.first().cloned().ok_or(Error::Invalid)?, parse_count(value.params.get(1))?, value.trailing.ok_or(Error::Invalid)?)),
+                    253 => Ok(Self::RplLUserUnknown(value.params.first().cloned().ok_or(Error::Invalid)?, parse_count(value.params.get(1))?, value.trailing.ok_or(Error::Invalid)?)),
+                    254 => Ok(Self::RplLUserChannels(value.params.first().cloned().ok_or(Error::Invalid)?, parse_count(value.params.get(1))?, value.trailing.ok_or(Error::Invalid)?)),
+                    255 => Ok(Self::RplLUserMe(value.params.first().cloned().ok_or(Error::Invalid)?, value.trailing.ok_or(Error::Invalid)?)),
                     265 => {
                         if value.params.len() == 1 {
-                            Ok(Self::RplLocalUsers(value.params.get(0).unwrap().clone(), None, value.trailing.unwrap()))
+                            Ok(Self::RplLocalUsers(value.params.first().cloned().ok_or(Error::Invalid)?, None, value.trailing.ok_or(Error::Invalid)?))
                         } else if value.params.len() == 3 {
-                            Ok(Self::RplLocalUsers(value.params.get(0).unwrap().clone(), Some((value.params.get(1).unwrap().parse::<u32>().unwrap(), value.params.get(2).unwrap().parse::<u32>().unwrap())), value.trailing.unwrap()))
+                            Ok(Self::RplLocalUsers(value.params.first().cloned().ok_or(Error::Invalid)?, Some((parse_count(value.params.get(1))?, parse_count(value.params.get(2))?)), value.trailing.ok_or(Error::Invalid)?))
                         } else {
                             Err(Error::Invalid)
                         }
                     },
                     266 => {
                         if value.params.len() == 1 {
-                            Ok(Self::RplGlobalUsers(value.params.get(0).unwrap().clone(), None, value.trailing.unwrap()))
+                            Ok(Self::RplGlobalUsers(value.params.first().cloned().ok_or(Error::Invalid)?, None, value.trailing.ok_or(Error::Invalid)?))
                         } else if value.params.len() == 3 {
-                            Ok(Self::RplGlobalUsers(value.params.get(0).unwrap().clone(), Some((value.params.get(1).unwrap().parse::<u32>().unwrap(), value.params.get(2).unwrap().parse::<u32>().unwrap())), value.trailing.unwrap()))
+                            Ok(Self::RplGlobalUsers(value.params.first().cloned().ok_or(Error::Invalid)?, Some((parse_count(value.params.get(1))?, parse_count(value.params.get(2))?)), value.trailing.ok_or(Error::Invalid)?))
                         } else {
                             Err(Error::Invalid)
                         }
                     },
-                    375 => Ok(Self::RplMotdStart(value.params.get(0).unwrap().clone(), value.trailing.unwrap())),
-                    372 => Ok(Self::RplMotd(value.params.get(0).unwrap().clone(), value.trailing.unwrap())),
-                    376 => Ok(Self::RplEndOfMotd(value.params.get(0).unwrap().clone(), value.trailing.unwrap())),
-                    396 => Ok(Self::RplHostHidden(value.params.get(0).unwrap().clone(), value.params.get(1).unwrap().clone(), value.trailing.unwrap())),
+                    311 => Ok(Self::RplWhoisUser {
+                        client: value.params.first().cloned().ok_or(Error::Invalid)?,
+                        nick: value.params.get(1).cloned().ok_or(Error::Invalid)?,
+                        username: value.params.get(2).cloned().ok_or(Error::Invalid)?,
+                        host: value.params.get(3).cloned().ok_or(Error::Invalid)?,
+                        realname: value.trailing.ok_or(Error::Invalid)?,
+                    }),
+                    312 => Ok(Self::RplWhoisServer(
+                        value.params.first().cloned().ok_or(Error::Invalid)?,
+                        value.params.get(1).cloned().ok_or(Error::Invalid)?,
+                        value.params.get(2).cloned().ok_or(Error::Invalid)?,
+                        value.trailing.unwrap_or_default(),
+                    )),
+                    313 => Ok(Self::RplWhoisOperator(value.params.first().cloned().ok_or(Error::Invalid)?, value.params.get(1).cloned().ok_or(Error::Invalid)?, value.trailing.unwrap_or_default())),
+                    314 => Ok(Self::RplWhowasUser {
+                        client: value.params.first().cloned().ok_or(Error::Invalid)?,
+                        nick: value.params.get(1).cloned().ok_or(Error::Invalid)?,
+                        username: value.params.get(2).cloned().ok_or(Error::Invalid)?,
+                        host: value.params.get(3).cloned().ok_or(Error::Invalid)?,
+                        realname: value.trailing.ok_or(Error::Invalid)?,
+                    }),
+                    317 => Ok(Self::RplWhoisIdle {
+                        client: value.params.first().cloned().ok_or(Error::Invalid)?,
+                        nick: value.params.get(1).cloned().ok_or(Error::Invalid)?,
+                        idle: value.params.get(2).ok_or(Error::Invalid)?.parse().map_err(|_| Error::Invalid)?,
+                        signon: match value.params.get(3) {
+                            Some(signon) => Some(signon.parse().map_err(|_| Error::Invalid)?),
+                            None => None,
+                        },
+                        message: value.trailing.unwrap_or_default(),
+                    }),
+                    318 => Ok(Self::RplEndOfWhois(value.params.first().cloned().ok_or(Error::Invalid)?, value.params.get(1).cloned().ok_or(Error::Invalid)?, value.trailing.unwrap_or_default())),
+                    // Some servers put the channels in middle params instead of the trailing
+                    319 => {
+                        let mut args = value.params.into_iter();
+                        let client = args.next().ok_or(Error::Invalid)?;
+                        let nick = args.next().ok_or(Error::Invalid)?;
+                        let channels = args.chain(value.trailing).flat_map(|m| m.split_whitespace().map(|m| m.to_string()).collect::<Vec<_>>()).collect();
+
+                        Ok(Self::RplWhoisChannels(client, nick, channels))
+                    },
+                    330 => Ok(Self::RplWhoisAccount(
+                        value.params.first().cloned().ok_or(Error::Invalid)?,
+                        value.params.get(1).cloned().ok_or(Error::Invalid)?,
+                        value.params.get(2).cloned().ok_or(Error::Invalid)?,
+                        value.trailing.unwrap_or_default(),
+                    )),
+                    324 => {
+                        let mut args = value.params.into_iter().chain(value.trailing);
+
+                        Ok(Self::RplChannelModeIs {
+                            client: args.next().ok_or(Error::Invalid)?,
+                            channel: args.next().ok_or(Error::Invalid)?,
+                            modes: args.next().ok_or(Error::Invalid)?,
+                            params: args.collect(),
+                        })
+                    },
+                    329 => Ok(Self::RplCreationTime(
+                        value.params.first().ok_or(Error::Invalid)?.clone(),
+                        value.params.get(1).ok_or(Error::Invalid)?.clone(),
+                        parse_count(value.params.get(2).or(value.trailing.as_ref()))?,
+                    )),
+                    331 => Ok(Self::RplNoTopic(value.params.first().cloned().ok_or(Error::Invalid)?, value.params.get(1).cloned().ok_or(Error::Invalid)?, value.trailing.ok_or(Error::Invalid)?)),
+                    332 => Ok(Self::RplTopic(value.params.first().cloned().ok_or(Error::Invalid)?, value.params.get(1).cloned().ok_or(Error::Invalid)?, value.trailing.ok_or(Error::Invalid)?)),
+                    333 => Ok(Self::RplTopicWhoTime(
+                        value.params.first().cloned().ok_or(Error::Invalid)?,
+                        value.params.get(1).cloned().ok_or(Error::Invalid)?,
+                        value.params.get(2).cloned().ok_or(Error::Invalid)?,
+                        value.params.get(3).or(value.trailing.as_ref()).ok_or(Error::Invalid)?.parse::<u64>().map_err(|_| Error::Invalid)?,
+                    )),
+                    354 => {
+                        let mut args = value.params.into_iter().chain(value.trailing);
+
+                        Ok(Self::RplWhoSpcRpl(args.next().ok_or(Error::Invalid)?, args.collect()))
+                    },
+                    346 => Ok(Self::RplInviteList {
+                        client: value.params.first().cloned().ok_or(Error::Invalid)?,
+                        channel: value.params.get(1).cloned().ok_or(Error::Invalid)?,
+                        mask: value.params.get(2).cloned().ok_or(Error::Invalid)?,
+                        set_by: value.params.get(3).cloned(),
+                        set_at: match value.params.get(4) {
+                            Some(time) => Some(time.parse::<u64>().map_err(|_| Error::Invalid)?),
+                            None => None,
+                        },
+                    }),
+                    347 => Ok(Self::RplEndOfInviteList(value.params.first().cloned().ok_or(Error::Invalid)?, value.params.get(1).cloned().ok_or(Error::Invalid)?, value.trailing.ok_or(Error::Invalid)?)),
+                    367 => Ok(Self::RplBanList {
+                        client: value.params.first().cloned().ok_or(Error::Invalid)?,
+                        channel: value.params.get(1).cloned().ok_or(Error::Invalid)?,
+                        mask: value.params.get(2).cloned().ok_or(Error::Invalid)?,
+                        set_by: value.params.get(3).cloned(),
+                        set_at: match value.params.get(4) {
+                            Some(time) => Some(time.parse::<u64>().map_err(|_| Error::Invalid)?),
+                            None => None,
+                        },
+                    }),
+                    368 => Ok(Self::RplEndOfBanList(value.params.first().cloned().ok_or(Error::Invalid)?, value.params.get(1).cloned().ok_or(Error::Invalid)?, value.trailing.ok_or(Error::Invalid)?)),
+                    369 => Ok(Self::RplEndOfWhowas(value.params.first().cloned().ok_or(Error::Invalid)?, value.params.get(1).cloned().ok_or(Error::Invalid)?, value.trailing.ok_or(Error::Invalid)?)),
+                    375 => Ok(Self::RplMotdStart(value.params.first().cloned().ok_or(Error::Invalid)?, value.trailing.ok_or(Error::Invalid)?)),
+                    372 => Ok(Self::RplMotd(value.params.first().cloned().ok_or(Error::Invalid)?, value.trailing.ok_or(Error::Invalid)?)),
+                    376 => Ok(Self::RplEndOfMotd(value.params.first().cloned().ok_or(Error::Invalid)?, value.trailing.ok_or(Error::Invalid)?)),
+                    353 => Ok(Self::RplNamReply {
+                        client: value.params.first().cloned().ok_or(Error::Invalid)?,
+                        symbol: value.params.get(1).cloned().ok_or(Error::Invalid)?,
+                        channel: value.params.get(2).cloned().ok_or(Error::Invalid)?,
+                        names: value.trailing.unwrap_or_default().split_whitespace().map(|m| m.to_string()).collect(),
+                    }),
+                    366 => Ok(Self::RplEndOfNames(
+                        value.params.first().cloned().ok_or(Error::Invalid)?,
+                        value.params.get(1).cloned().ok_or(Error::Invalid)?,
+                        value.trailing.unwrap_or_default(),
+                    )),
+                    396 => Ok(Self::RplHostHidden(value.params.first().cloned().ok_or(Error::Invalid)?, value.params.get(1).cloned().ok_or(Error::Invalid)?, value.trailing.ok_or(Error::Invalid)?)),
+                    600 | 601 | 604 | 605 => {
+                        let client = value.params.first().cloned().ok_or(Error::Invalid)?;
+                        let nick = value.params.get(1).cloned().ok_or(Error::Invalid)?;
+                        let username = value.params.get(2).cloned().ok_or(Error::Invalid)?;
+                        let host = value.params.get(3).cloned().ok_or(Error::Invalid)?;
+                        let since = parse_count(value.params.get(4))?;
+                        let message = value.trailing.unwrap_or_default();
+
+                        Ok(match command {
+                            600 => Self::RplLogOn(client, nick, username, host, since, message),
+                            601 => Self::RplLogOff(client, nick, username, host, since, message),
+                            604 => Self::RplNowOn(client, nick, username, host, since, message),
+                            _ => Self::RplNowOff(client, nick, username, host, since, message),
+                        })
+                    },
+                    730 => Ok(Self::RplMonOnline(
+                        value.params.first().cloned().ok_or(Error::Invalid)?,
+                        value.trailing.or(value.params.get(1).cloned()).ok_or(Error::Invalid)?.split(',').filter(|m| !m.is_empty()).map(|m| m.to_string()).collect(),
+                    )),
+                    731 => Ok(Self::RplMonOffline(
+                        value.params.first().cloned().ok_or(Error::Invalid)?,
+                        value.trailing.or(value.params.get(1).cloned()).ok_or(Error::Invalid)?.split(',').filter(|m| !m.is_empty()).map(|m| m.to_string()).collect(),
+                    )),
+                    406 => Ok(Self::ErrWasNoSuchNick(value.params.first().cloned().ok_or(Error::Invalid)?, value.params.get(1).cloned().ok_or(Error::Invalid)?, value.trailing.ok_or(Error::Invalid)?)),
+                    482 => Ok(Self::ErrChanOPrivsNeeded(value.params.first().cloned().ok_or(Error::Invalid)?, value.params.get(1).cloned().ok_or(Error::Invalid)?, value.trailing.ok_or(Error::Invalid)?)),
+                    903 => Ok(Self::RplSaslSuccess(value.params.first().cloned().ok_or(Error::Invalid)?, value.trailing.ok_or(Error::Invalid)?)),
+                    904 => Ok(Self::ErrSaslFail(value.params.first().cloned().ok_or(Error::Invalid)?, value.trailing.ok_or(Error::Invalid)?)),
                     _ => {
                         #[cfg(debug_assertions)]
                         {
@@ -223,6 +712,13 @@ impl TryFrom<GenericIrcCommand> for IrcCommand {
     }
 }
 
+// Counts and timestamps come straight from the server, which may send garbage or
+// numbers larger than expected
+fn parse_count(param: Option<&String>) -> Result<u64, Error> {
+    let param = param.ok_or(Error::Invalid)?;
+    param.parse::<u64>().map_err(|_| Error::ParseInt(param.clone()))
+}
+
 impl TryFrom<&str> for IrcCommand {
     type Error = Error;
 
@@ -244,20 +740,20 @@ impl From<IrcCommand> for GenericIrcCommand {
                 params: vec![nickname],
                 trailing: None,
             },
-            IrcCommand::User(username, realname) => GenericIrcCommand {
+            IrcCommand::User(username, mode, realname) => GenericIrcCommand {
                 command: GenericIrcCommandType::Text("USER".to_string()),
-                params: vec![username, "0".to_string(), "*".to_string(), realname],
+                params: vec![username, mode.to_string(), "*".to_string(), realname],
                 trailing: None,
-            },
+            }.promote_trailing(),
             IrcCommand::Ping(message) => GenericIrcCommand {
                 command: GenericIrcCommandType::Text("PING".to_string()),
                 params: vec![],
-                trailing: Some(message),
+                trailing: Some(message).filter(|m| !m.is_empty()),
             },
             IrcCommand::Pong(message) => GenericIrcCommand {
                 command: GenericIrcCommandType::Text("PONG".to_string()),
                 params: vec![],
-                trailing: Some(message),
+                trailing: Some(message).filter(|m| !m.is_empty()),
             },
             IrcCommand::Notice(target, message) => GenericIrcCommand {
                 command: GenericIrcCommandType::Text("NOTICE".to_string()),
@@ -269,19 +765,177 @@ impl From<IrcCommand> for GenericIrcCommand {
                 params: vec![],
                 trailing: Some(message),
             },
+            IrcCommand::Cap { client, subcommand, params: cap_params, capabilities } => {
+                let mut params = client.into_iter().collect::<Vec<_>>();
+                params.push(subcommand);
+                params.extend(cap_params);
+
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Text("CAP".to_string()),
+                    params,
+                    trailing: if capabilities.is_empty() {
+                        None
+                    } else {
+                        Some(capabilities.join(" "))
+                    },
+                }
+            },
+            IrcCommand::Away(message) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("AWAY".to_string()),
+                params: vec![],
+                trailing: message,
+            },
+            IrcCommand::Join(channels, keys) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("JOIN".to_string()),
+                params: [channels].into_iter().chain(keys).collect(),
+                trailing: None,
+            },
+            IrcCommand::Part(channels, reason) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("PART".to_string()),
+                params: vec![channels],
+                trailing: reason,
+            },
+            IrcCommand::Kick(channel, nick, reason) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("KICK".to_string()),
+                params: vec![channel, nick],
+                trailing: reason,
+            },
+            IrcCommand::Quit(reason) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("QUIT".to_string()),
+                params: vec![],
+                trailing: reason,
+            },
+            IrcCommand::Invite(nick, channel) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("INVITE".to_string()),
+                params: vec![nick, channel],
+                trailing: None,
+            },
+            IrcCommand::Kill(nick, reason) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("KILL".to_string()),
+                params: vec![nick],
+                trailing: Some(reason),
+            },
+            IrcCommand::Privmsg(target, text) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("PRIVMSG".to_string()),
+                params: vec![target],
+                trailing: Some(text),
+            },
+            IrcCommand::Names(channels) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("NAMES".to_string()),
+                params: channels.into_iter().collect(),
+                trailing: None,
+            },
+            IrcCommand::List(channels) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("LIST".to_string()),
+                params: channels.into_iter().collect(),
+                trailing: None,
+            },
+            IrcCommand::Who(mask, flags) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("WHO".to_string()),
+                params: [mask].into_iter().chain(flags).collect(),
+                trailing: None,
+            },
+            IrcCommand::Whois(nicks, server) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("WHOIS".to_string()),
+                params: server.into_iter().chain([nicks]).collect(),
+                trailing: None,
+            },
+            IrcCommand::Oper(name, password) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("OPER".to_string()),
+                params: vec![name, password],
+                trailing: None,
+            },
+            IrcCommand::Wallops(text) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("WALLOPS".to_string()),
+                params: vec![],
+                trailing: Some(text),
+            },
+            IrcCommand::Ison(nicks) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("ISON".to_string()),
+                params: nicks,
+                trailing: None,
+            },
+            IrcCommand::Userhost(nicks) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("USERHOST".to_string()),
+                params: nicks,
+                trailing: None,
+            },
+            IrcCommand::Watch(entries) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("WATCH".to_string()),
+                params: entries,
+                trailing: None,
+            },
+            IrcCommand::Monitor(subcommand, targets) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("MONITOR".to_string()),
+                params: std::iter::once(subcommand).chain(targets).collect(),
+                trailing: None,
+            },
+            IrcCommand::Batch(reference, params) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("BATCH".to_string()),
+                params: [reference].into_iter().chain(params).collect(),
+                trailing: None,
+            },
+            IrcCommand::ChgHost(user, host) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("CHGHOST".to_string()),
+                params: vec![user, host],
+                trailing: None,
+            },
+            IrcCommand::Account(account) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("ACCOUNT".to_string()),
+                params: vec![account],
+                trailing: None,
+            },
+            IrcCommand::SetName(realname) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("SETNAME".to_string()),
+                params: vec![],
+                trailing: Some(realname),
+            },
+            IrcCommand::TagMsg(target) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("TAGMSG".to_string()),
+                params: vec![target],
+                trailing: None,
+            },
+            IrcCommand::Topic(channel, topic) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("TOPIC".to_string()),
+                params: vec![channel],
+                trailing: topic,
+            },
+            IrcCommand::Mode(target, modes) => {
+                let mut params = vec![target];
+                params.extend(modes);
+
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Text("MODE".to_string()),
+                    params,
+                    trailing: None,
+                }
+            },
+            IrcCommand::Whowas(nick, count) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("WHOWAS".to_string()),
+                params: match count {
+                    Some(count) => vec![nick, count.to_string()],
+                    None => vec![nick],
+                },
+                trailing: None,
+            },
+            IrcCommand::Authenticate(data) => GenericIrcCommand {
+                command: GenericIrcCommandType::Text("AUTHENTICATE".to_string()),
+                params: vec![data],
+                trailing: None,
+            },
 
             IrcCommand::RplWelcome(client, message) => GenericIrcCommand {
-                command: GenericIrcCommandType::Number(001),
+                command: GenericIrcCommandType::Number(1),
                 params: vec![client],
                 trailing: Some(message),
             },
             IrcCommand::RplYourHost(client, message) => GenericIrcCommand {
-                command: GenericIrcCommandType::Number(002),
+                command: GenericIrcCommandType::Number(2),
                 params: vec![client],
                 trailing: Some(message),
             },
             IrcCommand::RplCreated(client, message) => GenericIrcCommand {
-                command: GenericIrcCommandType::Number(003),
+                command: GenericIrcCommandType::Number(3),
                 params: vec![client],
                 trailing: Some(message),
             },
@@ -293,7 +947,7 @@ impl From<IrcCommand> for GenericIrcCommand {
                 cmodes,
                 cmodes_params
             } => GenericIrcCommand {
-                command: GenericIrcCommandType::Number(004),
+                command: GenericIrcCommandType::Number(4),
                 params: if let Some(cmodes_params) = cmodes_params {
                     vec![client, servername, version, umodes, cmodes, cmodes_params]
                 } else {
@@ -306,12 +960,19 @@ impl From<IrcCommand> for GenericIrcCommand {
                 params.extend(caps);
 
                 GenericIrcCommand {
-                    command: GenericIrcCommandType::Number(005),
+                    command: GenericIrcCommandType::Number(5),
                     params,
                     trailing: Some(message),
                 }
             },
 
+            IrcCommand::RplUModeIs(client, modes) => {
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(221),
+                    params: vec![client, modes],
+                    trailing: None,
+                }
+            }
             IrcCommand::RplLUserClient(client, message) => {
                 GenericIrcCommand {
                     command: GenericIrcCommandType::Number(251),
@@ -369,6 +1030,157 @@ impl From<IrcCommand> for GenericIrcCommand {
                 }
             },
 
+            IrcCommand::RplWhoisUser { client, nick, username, host, realname } => {
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(311),
+                    params: vec![client, nick, username, host, "*".to_string()],
+                    trailing: Some(realname),
+                }
+            },
+            IrcCommand::RplWhoisServer(client, nick, server, info) => {
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(312),
+                    params: vec![client, nick, server],
+                    trailing: Some(info),
+                }
+            },
+            IrcCommand::RplWhoisOperator(client, nick, message) => {
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(313),
+                    params: vec![client, nick],
+                    trailing: Some(message),
+                }
+            },
+            IrcCommand::RplWhowasUser { client, nick, username, host, realname } => {
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(314),
+                    params: vec![client, nick, username, host, "*".to_string()],
+                    trailing: Some(realname),
+                }
+            },
+            IrcCommand::RplWhoisIdle { client, nick, idle, signon, message } => {
+                let mut params = vec![client, nick, idle.to_string()];
+                params.extend(signon.map(|m| m.to_string()));
+
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(317),
+                    params,
+                    trailing: Some(message),
+                }
+            },
+            IrcCommand::RplEndOfWhois(client, nick, message) => {
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(318),
+                    params: vec![client, nick],
+                    trailing: Some(message),
+                }
+            },
+            IrcCommand::RplWhoisChannels(client, nick, channels) => {
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(319),
+                    params: vec![client, nick],
+                    trailing: Some(channels.join(" ")),
+                }
+            },
+            IrcCommand::RplWhoisAccount(client, nick, account, message) => {
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(330),
+                    params: vec![client, nick, account],
+                    trailing: Some(message),
+                }
+            },
+
+            IrcCommand::RplChannelModeIs { client, channel, modes, params } => {
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(324),
+                    params: [client, channel, modes].into_iter().chain(params).collect(),
+                    trailing: None,
+                }
+            },
+            IrcCommand::RplCreationTime(client, channel, time) => {
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(329),
+                    params: vec![client, channel, time.to_string()],
+                    trailing: None,
+                }
+            },
+
+            IrcCommand::RplNoTopic(client, channel, message) => {
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(331),
+                    params: vec![client, channel],
+                    trailing: Some(message),
+                }
+            },
+            IrcCommand::RplTopic(client, channel, topic) => {
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(332),
+                    params: vec![client, channel],
+                    trailing: Some(topic),
+                }
+            },
+            IrcCommand::RplTopicWhoTime(client, channel, setter, time) => {
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(333),
+                    params: vec![client, channel, setter, time.to_string()],
+                    trailing: None,
+                }
+            },
+
+            IrcCommand::RplEndOfWhowas(client, nick, message) => {
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(369),
+                    params: vec![client, nick],
+                    trailing: Some(message),
+                }
+            },
+
+            IrcCommand::RplBanList { client, channel, mask, set_by, set_at } => {
+                let mut params = vec![client, channel, mask];
+                params.extend(set_by);
+                params.extend(set_at.map(|m| m.to_string()));
+
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(367),
+                    params,
+                    trailing: None,
+                }
+            },
+            IrcCommand::RplWhoSpcRpl(client, mut fields) => {
+                let trailing = fields.pop();
+
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(354),
+                    params: std::iter::once(client).chain(fields).collect(),
+                    trailing,
+                }
+            },
+            IrcCommand::RplEndOfBanList(client, channel, message) => {
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(368),
+                    params: vec![client, channel],
+                    trailing: Some(message),
+                }
+            },
+            IrcCommand::RplInviteList { client, channel, mask, set_by, set_at } => {
+                let mut params = vec![client, channel, mask];
+                params.extend(set_by);
+                params.extend(set_at.map(|m| m.to_string()));
+
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(346),
+                    params,
+                    trailing: None,
+                }
+            },
+            IrcCommand::RplEndOfInviteList(client, channel, message) => {
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(347),
+                    params: vec![client, channel],
+                    trailing: Some(message),
+                }
+            },
+
             IrcCommand::RplMotdStart(client, message) => {
                 GenericIrcCommand {
                     command: GenericIrcCommandType::Number(375),
@@ -391,6 +1203,16 @@ impl From<IrcCommand> for GenericIrcCommand {
                 }
             },
 
+            IrcCommand::RplNamReply { client, symbol, channel, names } => GenericIrcCommand {
+                command: GenericIrcCommandType::Number(353),
+                params: vec![client, symbol, channel],
+                trailing: Some(names.join(" ")),
+            },
+            IrcCommand::RplEndOfNames(client, channel, message) => GenericIrcCommand {
+                command: GenericIrcCommandType::Number(366),
+                params: vec![client, channel],
+                trailing: Some(message),
+            },
             IrcCommand::RplHostHidden(client, host, message) => {
                 GenericIrcCommand {
                     command: GenericIrcCommandType::Number(396),
@@ -399,6 +1221,67 @@ impl From<IrcCommand> for GenericIrcCommand {
                 }
             },
 
+            IrcCommand::RplLogOn(client, nick, username, host, since, message) => GenericIrcCommand {
+                command: GenericIrcCommandType::Number(600),
+                params: vec![client, nick, username, host, since.to_string()],
+                trailing: Some(message),
+            },
+            IrcCommand::RplLogOff(client, nick, username, host, since, message) => GenericIrcCommand {
+                command: GenericIrcCommandType::Number(601),
+                params: vec![client, nick, username, host, since.to_string()],
+                trailing: Some(message),
+            },
+            IrcCommand::RplNowOn(client, nick, username, host, since, message) => GenericIrcCommand {
+                command: GenericIrcCommandType::Number(604),
+                params: vec![client, nick, username, host, since.to_string()],
+                trailing: Some(message),
+            },
+            IrcCommand::RplNowOff(client, nick, username, host, since, message) => GenericIrcCommand {
+                command: GenericIrcCommandType::Number(605),
+                params: vec![client, nick, username, host, since.to_string()],
+                trailing: Some(message),
+            },
+            IrcCommand::RplMonOnline(client, targets) => GenericIrcCommand {
+                command: GenericIrcCommandType::Number(730),
+                params: vec![client],
+                trailing: Some(targets.join(",")),
+            },
+            IrcCommand::RplMonOffline(client, targets) => GenericIrcCommand {
+                command: GenericIrcCommandType::Number(731),
+                params: vec![client],
+                trailing: Some(targets.join(",")),
+            },
+
+            IrcCommand::ErrWasNoSuchNick(client, nick, message) => {
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(406),
+                    params: vec![client, nick],
+                    trailing: Some(message),
+                }
+            },
+            IrcCommand::ErrChanOPrivsNeeded(client, channel, message) => {
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(482),
+                    params: vec![client, channel],
+                    trailing: Some(message),
+                }
+            },
+
+            IrcCommand::RplSaslSuccess(client, message) => {
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(903),
+                    params: vec![client],
+                    trailing: Some(message),
+                }
+            },
+            IrcCommand::ErrSaslFail(client, message) => {
+                GenericIrcCommand {
+                    command: GenericIrcCommandType::Number(904),
+                    params: vec![client],
+                    trailing: Some(message),
+                }
+            },
+
             IrcCommand::Generic(generic) => generic,
         }
     }
@@ -424,20 +1307,12 @@ impl TryFrom<&str> for GenericIrcCommandType {
     type Error = Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        match value.chars().next().unwrap() {
-            '0'..='9' => {
-                if value.len() == 3 && value.chars().all(|c| c.is_numeric()) {
-                    Ok(Self::Number(value.parse::<u16>().unwrap()))
-                } else {
-                    Err(Error::Invalid)
-                }
+        match value.chars().next() {
+            Some('0'..='9') if value.len() == 3 && value.chars().all(|c| c.is_ascii_digit()) => {
+                Ok(Self::Number(value.parse::<u16>().map_err(|_| Error::Invalid)?))
             },
-            'A'..='Z' => {
-                if value.chars().all(|c| c.is_ascii_uppercase()) {
-                    Ok(Self::Text(value.to_string()))
-                } else {
-                    Err(Error::Invalid)
-                }
+            Some('A'..='Z') if value.chars().all(|c| c.is_ascii_uppercase()) => {
+                Ok(Self::Text(value.to_string()))
             },
             _ => {
                 Err(Error::Invalid)
@@ -460,16 +1335,34 @@ impl From<GenericIrcCommandType> for String {
 #[derive(Debug, PartialEq, Clone)]
 pub struct GenericIrcCommand {
     pub command: GenericIrcCommandType,
-    // TODO: Store trailing seperately
+    // Never includes the trailing, and each one must be sendable as is: not empty, no
+    // spaces and no leading ":"
     pub params: Vec<String>,
+    // The parameter after " :", the only one that may contain spaces
     pub trailing: Option<String>,
 }
 
+impl GenericIrcCommand {
+    // For commands built the old way, with free text as the last param. Moves the last param
+    // into the trailing when it can't be sent as a normal param and there is no trailing yet
+    pub fn promote_trailing(mut self) -> Self {
+        if self.trailing.is_none() && self.params.last().is_some_and(|m| !is_middle_param(m)) {
+            self.trailing = self.params.pop();
+        }
+
+        self
+    }
+}
+
+fn is_middle_param(param: &str) -> bool {
+    !param.is_empty() && !param.starts_with(':') && !param.contains([' ', '\r', '\n', '\0'])
+}
+
 impl TryFrom<&str> for GenericIrcCommand {
     type Error = Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let re = Regex::new("^([A-Z]+|[0-9]{3})((?: (?:[^:\\n\\r\\x00 ][^\\n\\r\\x00 ]*))*)?(?: :([^\\n\\r]+))?$").unwrap();
+        let re = Regex::new("^([A-Z]+|[0-9]{3})((?: (?:[^:\\n\\r\\x00 ][^\\n\\r\\x00 ]*))*)?(?: :([^\\n\\r]*))?$").unwrap();
 
         let Some(caps) = re.captures(value) else {
             return Err(Error::NoMatch(value.to_string()));
@@ -489,7 +1382,7 @@ impl TryFrom<&str> for GenericIrcCommand {
                 if trimmed.is_empty() {
                     vec![]
                 } else {
-                    trimmed.split(' ').into_iter().collect::<Vec<_>>()
+                    trimmed.split(' ').collect::<Vec<_>>()
                 }
             }
         }.into_iter().map(|m| m.to_string()).collect();
@@ -507,27 +1400,20 @@ impl TryFrom<&str> for GenericIrcCommand {
 impl TryFrom<GenericIrcCommand> for String {
     type Error = Error;
 
+    // Params are written as they are, so one that can only be sent as the trailing is an
+    // error rather than quietly turned into it, see GenericIrcCommand::promote_trailing
     fn try_from(value: GenericIrcCommand) -> Result<Self, Error> {
         let mut buffer = String::new();
 
         buffer.push_str(String::from(value.command).as_str());
 
-        if !value.params.is_empty() {
-            let last = value.params.last().unwrap();
-
-            let params = value.params.iter().take(value.params.len() - 1);
-
-            if !params.clone().all(|p| !p.contains(' ')) { return Err(Error::Invalid) };
-
-            for param in params {
-                buffer.push_str(format!(" {}", param.as_str()).as_str());
-            };
-
-            if last.contains(' ') {
-                buffer.push_str(format!(" :{}", last).as_str());
-            } else {
-                buffer.push_str(format!(" {}", last).as_str());
+        for param in value.params {
+            if !is_middle_param(&param) {
+                return Err(Error::Invalid);
             }
+
+            buffer.push(' ');
+            buffer.push_str(&param);
         }
 
         if let Some(trailing) = value.trailing {
@@ -560,11 +1446,7 @@ mod tests {
         assert_eq!(":server PRIVMSG #meme :11/10 cock\r\n".try_into(), Ok(IrcMessage {
             tags: vec![],
             prefix: Some("server".to_string()),
-            command: IrcCommand::Generic(GenericIrcCommand {
-                command: GenericIrcCommandType::Text("PRIVMSG".to_string()),
-                params: vec!["#meme".to_string()],
-                trailing: Some("11/10 cock".to_string()),
-            }),
+            command: IrcCommand::Privmsg("#meme".to_string(), "11/10 cock".to_string()),
         }));
 
         assert_eq!(":server 404 :shit\r\n".try_into(), Ok(IrcMessage {
@@ -600,6 +1482,47 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn tag_values() {
+        let s = |m: &str| m.to_string();
+        let tags = vec![(s("key"), None), (s("empty"), Some(s(""))), (s("full"), Some(s("val")))];
+
+        let message = IrcMessage::try_from("@key;empty=;full=val PING :x\r\n").unwrap();
+        assert_eq!(message.tags, tags);
+        assert_eq!(message.get_tag("key"), Some(None));
+        assert_eq!(message.get_tag("empty"), Some(Some("")));
+        assert_eq!(message.get_tag("full"), Some(Some("val")));
+        assert_eq!(message.get_tag("missing"), None);
+
+        // Only the first "=" separates the key
+        assert_eq!(IrcMessage::try_from("@full=a=b PING :x\r\n").unwrap().get_tag("full"), Some(Some("a=b")));
+
+        let line = String::try_from(IrcMessage {
+            tags,
+            prefix: None,
+            command: IrcCommand::Ping(s("x")),
+        }).unwrap();
+        assert_eq!(line, "@key;empty=;full=val PING :x\r\n");
+        assert_eq!(IrcMessage::try_from(line.as_str()).unwrap(), message);
+    }
+
+    #[test]
+    fn text_without_colon() {
+        // A single word doesn't need the colon
+        assert_eq!(":Bobby!bob@example.com NOTICE Jimmy hello\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("Bobby!bob@example.com".to_string()),
+            command: IrcCommand::Notice("Jimmy".to_string(), "hello".to_string()),
+        }));
+        assert_eq!(":Bobby!bob@example.com PRIVMSG #rust hello\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("Bobby!bob@example.com".to_string()),
+            command: IrcCommand::Privmsg("#rust".to_string(), "hello".to_string()),
+        }));
+
+        assert_eq!(IrcMessage::try_from("NOTICE Jimmy\r\n"), Err(Error::Invalid));
+    }
+
     #[test]
     fn to_string() {
         assert_eq!("LEAVE\r\n".to_string(), String::try_from(IrcMessage {
@@ -657,7 +1580,7 @@ mod tests {
             trailing: None,
         }.try_into().unwrap());
 
-        assert_eq!(IrcCommand::User("Jim1982".to_string(), "James Bond".to_string()), GenericIrcCommand {
+        assert_eq!(IrcCommand::User("Jim1982".to_string(), 0, "James Bond".to_string()), GenericIrcCommand {
             command: GenericIrcCommandType::Text("USER".to_string()),
             params: vec!["Jim1982".to_string(), "James Bond".to_string()],
             trailing: None,
@@ -667,6 +1590,655 @@ mod tests {
 
         assert_eq!(String::try_from(IrcCommand::Nick("Jimmy".to_string())).unwrap(), "NICK Jimmy".to_string());
 
-        assert_eq!(String::try_from(IrcCommand::User("Jim1982".to_string(), "James Bond".to_string())).unwrap(), "USER Jim1982 0 * :James Bond".to_string());
+        assert_eq!(String::try_from(IrcCommand::User("Jim1982".to_string(), 0, "James Bond".to_string())).unwrap(), "USER Jim1982 0 * :James Bond".to_string());
+
+        assert_eq!(IrcCommand::try_from("USER Jim1982 8 * :James Bond"), Ok(IrcCommand::User("Jim1982".to_string(), 8, "James Bond".to_string())));
+        assert_eq!(String::try_from(IrcCommand::User("Jim1982".to_string(), 12, "James Bond".to_string())).unwrap(), "USER Jim1982 12 * :James Bond".to_string());
+    }
+
+    #[test]
+    fn sasl() {
+        assert_eq!(":irc.example.net CAP * LS * :multi-prefix sasl=PLAIN,EXTERNAL\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("irc.example.net".to_string()),
+            command: IrcCommand::Cap {
+                client: Some("*".to_string()),
+                subcommand: "LS".to_string(),
+                params: vec!["*".to_string()],
+                capabilities: vec!["multi-prefix".to_string(), "sasl=PLAIN,EXTERNAL".to_string()],
+            },
+        }));
+
+        assert_eq!(":irc.example.net CAP Jimmy ACK sasl\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("irc.example.net".to_string()),
+            command: IrcCommand::Cap {
+                client: Some("Jimmy".to_string()),
+                subcommand: "ACK".to_string(),
+                params: vec![],
+                capabilities: vec!["sasl".to_string()],
+            },
+        }));
+
+        assert_eq!("AUTHENTICATE +\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: None,
+            command: IrcCommand::Authenticate("+".to_string()),
+        }));
+
+        assert_eq!(":irc.example.net 904 Jimmy :SASL authentication failed\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("irc.example.net".to_string()),
+            command: IrcCommand::ErrSaslFail("Jimmy".to_string(), "SASL authentication failed".to_string()),
+        }));
+
+        assert_eq!(String::try_from(IrcCommand::Cap {
+            client: None,
+            subcommand: "LS".to_string(),
+            params: vec!["302".to_string()],
+            capabilities: vec![],
+        }).unwrap(), "CAP LS 302".to_string());
+
+        assert_eq!(String::try_from(IrcCommand::Cap {
+            client: None,
+            subcommand: "REQ".to_string(),
+            params: vec![],
+            capabilities: vec!["sasl".to_string(), "multi-prefix".to_string()],
+        }).unwrap(), "CAP REQ :sasl multi-prefix".to_string());
+
+        assert_eq!(String::try_from(IrcCommand::Authenticate("EXTERNAL".to_string())).unwrap(), "AUTHENTICATE EXTERNAL".to_string());
+    }
+
+    #[test]
+    fn helpers() {
+        let message = IrcMessage::try_from("@time=2024-01-01T00:00:00.000Z;draft/bot :irc.example.net 001 Jimmy :Welcome to the network\r\n").unwrap();
+
+        assert_eq!(message.numeric(), Some(1));
+        assert_eq!(message.sender_nick(), None);
+        assert_eq!(message.get_tag("time"), Some(Some("2024-01-01T00:00:00.000Z")));
+        assert_eq!(message.get_tag("draft/bot"), Some(None));
+        assert_eq!(message.get_tag("msgid"), None);
+
+        let message = IrcMessage::try_from("@msgid=abc :Jimmy!jim@example.com NOTICE #rust :hello\r\n").unwrap();
+
+        assert_eq!(message.numeric(), None);
+        assert_eq!(message.sender_nick(), Some("Jimmy"));
+        assert_eq!(message.get_tag("msgid"), Some(Some("abc")));
+        assert_eq!(message.msgid(), Some("abc"));
+        assert_eq!(message.server_time(), None);
+    }
+
+    #[test]
+    fn server_time() {
+        let message = IrcMessage::try_from("@time=2024-03-01T12:30:05.250Z :irc.example.net NOTICE * :hello\r\n").unwrap();
+        assert_eq!(message.server_time(), Some(SystemTime::UNIX_EPOCH + Duration::from_millis(1709296205250)));
+
+        assert_eq!(parse_server_time("1970-01-01T00:00:00Z"), Some(SystemTime::UNIX_EPOCH));
+        assert_eq!(parse_server_time("2000-02-29T23:59:59.5Z"), Some(SystemTime::UNIX_EPOCH + Duration::from_millis(951868799500)));
+
+        for value in ["2024-03-01T12:30:05", "2024-13-01T00:00:00Z", "2024-03-01 12:30:05Z", "1969-12-31T23:59:59Z", "yesterday"] {
+            assert_eq!(parse_server_time(value), None);
+        }
+    }
+
+    #[test]
+    fn tagmsg() {
+        assert_eq!("@+typing=active :Jimmy!jim@example.com TAGMSG #rust\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![("+typing".to_string(), Some("active".to_string()))],
+            prefix: Some("Jimmy!jim@example.com".to_string()),
+            command: IrcCommand::TagMsg("#rust".to_string()),
+        }));
+
+        assert_eq!(String::try_from(IrcMessage {
+            tags: vec![("+draft/react".to_string(), Some("lol".to_string())), ("+draft/reply".to_string(), Some("abc".to_string()))],
+            prefix: None,
+            command: IrcCommand::TagMsg("#rust".to_string()),
+        }).unwrap(), "@+draft/react=lol;+draft/reply=abc TAGMSG #rust\r\n".to_string());
+    }
+
+    #[test]
+    fn topic() {
+        assert_eq!(":Jimmy!jim@example.com TOPIC #rust :Rust discussion\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("Jimmy!jim@example.com".to_string()),
+            command: IrcCommand::Topic("#rust".to_string(), Some("Rust discussion".to_string())),
+        }));
+
+        // Clearing the topic leaves an empty trailing parameter
+        assert_eq!(":Jimmy!jim@example.com TOPIC #rust :\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("Jimmy!jim@example.com".to_string()),
+            command: IrcCommand::Topic("#rust".to_string(), Some("".to_string())),
+        }));
+
+        assert_eq!(":irc.example.net 332 Jimmy #rust :Rust discussion\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("irc.example.net".to_string()),
+            command: IrcCommand::RplTopic("Jimmy".to_string(), "#rust".to_string(), "Rust discussion".to_string()),
+        }));
+
+        assert_eq!(":irc.example.net 333 Jimmy #rust Ferris!ferris@example.com 1700000000\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("irc.example.net".to_string()),
+            command: IrcCommand::RplTopicWhoTime("Jimmy".to_string(), "#rust".to_string(), "Ferris!ferris@example.com".to_string(), 1700000000),
+        }));
+
+        assert_eq!(String::try_from(IrcCommand::Topic("#rust".to_string(), Some("Rust discussion".to_string()))).unwrap(), "TOPIC #rust :Rust discussion".to_string());
+        assert_eq!(String::try_from(IrcCommand::Topic("#rust".to_string(), None)).unwrap(), "TOPIC #rust".to_string());
+    }
+
+    #[test]
+    fn channel_modes() {
+        assert_eq!(":irc.example.net 324 Jimmy #rust +ntkl secret 50\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("irc.example.net".to_string()),
+            command: IrcCommand::RplChannelModeIs {
+                client: "Jimmy".to_string(),
+                channel: "#rust".to_string(),
+                modes: "+ntkl".to_string(),
+                params: vec!["secret".to_string(), "50".to_string()],
+            },
+        }));
+
+        assert_eq!(":irc.example.net 329 Jimmy #rust :1600000000\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("irc.example.net".to_string()),
+            command: IrcCommand::RplCreationTime("Jimmy".to_string(), "#rust".to_string(), 1600000000),
+        }));
+
+        assert_eq!(String::try_from(IrcCommand::RplChannelModeIs {
+            client: "Jimmy".to_string(),
+            channel: "#rust".to_string(),
+            modes: "+nt".to_string(),
+            params: vec![],
+        }).unwrap(), "324 Jimmy #rust +nt".to_string());
+    }
+
+    #[test]
+    fn whox_replies() {
+        assert_eq!(":irc.example.net 354 Jimmy 152 #rust Ferris H@ ferris :Ferris the Crab\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("irc.example.net".to_string()),
+            command: IrcCommand::RplWhoSpcRpl("Jimmy".to_string(), vec![
+                "152".to_string(),
+                "#rust".to_string(),
+                "Ferris".to_string(),
+                "H@".to_string(),
+                "ferris".to_string(),
+                "Ferris the Crab".to_string(),
+            ]),
+        }));
+
+        assert_eq!(String::try_from(IrcCommand::RplWhoSpcRpl("Jimmy".to_string(), vec![
+            "152".to_string(),
+            "Ferris".to_string(),
+            "0".to_string(),
+        ])).unwrap(), "354 Jimmy 152 Ferris :0".to_string());
+    }
+
+    #[test]
+    fn ban_list() {
+        assert_eq!(":irc.example.net 367 Jimmy #rust *!*@spam.example.com Ferris 1700000000\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("irc.example.net".to_string()),
+            command: IrcCommand::RplBanList {
+                client: "Jimmy".to_string(),
+                channel: "#rust".to_string(),
+                mask: "*!*@spam.example.com".to_string(),
+                set_by: Some("Ferris".to_string()),
+                set_at: Some(1700000000),
+            },
+        }));
+
+        // The setter and time are optional
+        assert_eq!(":irc.example.net 367 Jimmy #rust *!*@spam.example.com\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("irc.example.net".to_string()),
+            command: IrcCommand::RplBanList {
+                client: "Jimmy".to_string(),
+                channel: "#rust".to_string(),
+                mask: "*!*@spam.example.com".to_string(),
+                set_by: None,
+                set_at: None,
+            },
+        }));
+
+        assert_eq!(String::try_from(IrcCommand::Mode("#rust".to_string(), vec!["+b".to_string()])).unwrap(), "MODE #rust +b".to_string());
+    }
+
+    #[test]
+    fn invites() {
+        assert_eq!(":Ferris!ferris@crab.example.com INVITE Jimmy #rust\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("Ferris!ferris@crab.example.com".to_string()),
+            command: IrcCommand::Invite("Jimmy".to_string(), "#rust".to_string()),
+        }));
+        assert_eq!(IrcCommand::try_from("INVITE Jimmy :#rust"), Ok(IrcCommand::Invite("Jimmy".to_string(), "#rust".to_string())));
+        assert_eq!(String::try_from(IrcCommand::Invite("Jimmy".to_string(), "#rust".to_string())).unwrap(), "INVITE Jimmy #rust".to_string());
+
+        assert_eq!(":irc.example.net 346 Jimmy #rust *!*@friends.example.com Ferris 1700000000\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("irc.example.net".to_string()),
+            command: IrcCommand::RplInviteList {
+                client: "Jimmy".to_string(),
+                channel: "#rust".to_string(),
+                mask: "*!*@friends.example.com".to_string(),
+                set_by: Some("Ferris".to_string()),
+                set_at: Some(1700000000),
+            },
+        }));
+        assert_eq!(":irc.example.net 347 Jimmy #rust :End of Channel Invite List\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("irc.example.net".to_string()),
+            command: IrcCommand::RplEndOfInviteList("Jimmy".to_string(), "#rust".to_string(), "End of Channel Invite List".to_string()),
+        }));
+    }
+
+    #[test]
+    fn whowas() {
+        assert_eq!(":irc.example.net 314 Jimmy Ferris ferris crab.example.com * :Ferris the Crab\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("irc.example.net".to_string()),
+            command: IrcCommand::RplWhowasUser {
+                client: "Jimmy".to_string(),
+                nick: "Ferris".to_string(),
+                username: "ferris".to_string(),
+                host: "crab.example.com".to_string(),
+                realname: "Ferris the Crab".to_string(),
+            },
+        }));
+
+        assert_eq!(":irc.example.net 406 Jimmy Ghost :There was no such nickname\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("irc.example.net".to_string()),
+            command: IrcCommand::ErrWasNoSuchNick("Jimmy".to_string(), "Ghost".to_string(), "There was no such nickname".to_string()),
+        }));
+
+        assert_eq!(String::try_from(IrcCommand::Whowas("Ferris".to_string(), Some(2))).unwrap(), "WHOWAS Ferris 2".to_string());
+        assert_eq!(String::try_from(IrcCommand::Whowas("Ferris".to_string(), None)).unwrap(), "WHOWAS Ferris".to_string());
+    }
+
+    #[test]
+    fn user_modes() {
+        assert_eq!(IrcMessage::try_from(":irc.example.net 221 Jimmy +iw\r\n").unwrap().command, IrcCommand::RplUModeIs("Jimmy".to_string(), "+iw".to_string()));
+        assert_eq!(IrcMessage::try_from(":irc.example.net 221 Jimmy :+iw\r\n").unwrap().command, IrcCommand::RplUModeIs("Jimmy".to_string(), "+iw".to_string()));
+        assert!(IrcMessage::try_from(":irc.example.net 221 Jimmy\r\n").is_err());
+        assert_eq!(String::try_from(IrcCommand::RplUModeIs("Jimmy".to_string(), "+iw".to_string())).unwrap(), "221 Jimmy +iw".to_string());
+    }
+
+    #[test]
+    fn whois() {
+        let s = |m: &str| m.to_string();
+
+        let replies = [
+            (":irc.example.net 311 Jimmy Ferris ferris crab.example.com * :Ferris the Crab", IrcCommand::RplWhoisUser {
+                client: s("Jimmy"),
+                nick: s("Ferris"),
+                username: s("ferris"),
+                host: s("crab.example.com"),
+                realname: s("Ferris the Crab"),
+            }),
+            (":irc.example.net 312 Jimmy Ferris irc.example.net :Example server", IrcCommand::RplWhoisServer(s("Jimmy"), s("Ferris"), s("irc.example.net"), s("Example server"))),
+            (":irc.example.net 313 Jimmy Ferris :is an IRC operator", IrcCommand::RplWhoisOperator(s("Jimmy"), s("Ferris"), s("is an IRC operator"))),
+            (":irc.example.net 317 Jimmy Ferris 42 1700000000 :seconds idle, signon time", IrcCommand::RplWhoisIdle {
+                client: s("Jimmy"),
+                nick: s("Ferris"),
+                idle: 42,
+                signon: Some(1700000000),
+                message: s("seconds idle, signon time"),
+            }),
+            (":irc.example.net 317 Jimmy Ferris 42 :seconds idle", IrcCommand::RplWhoisIdle {
+                client: s("Jimmy"),
+                nick: s("Ferris"),
+                idle: 42,
+                signon: None,
+                message: s("seconds idle"),
+            }),
+            (":irc.example.net 318 Jimmy Ferris :End of /WHOIS list.", IrcCommand::RplEndOfWhois(s("Jimmy"), s("Ferris"), s("End of /WHOIS list."))),
+            (":irc.example.net 319 Jimmy Ferris :@#rust +#go #irc", IrcCommand::RplWhoisChannels(s("Jimmy"), s("Ferris"), vec![s("@#rust"), s("+#go"), s("#irc")])),
+            (":irc.example.net 330 Jimmy Ferris ferris :is logged in as", IrcCommand::RplWhoisAccount(s("Jimmy"), s("Ferris"), s("ferris"), s("is logged in as"))),
+        ];
+
+        for (line, command) in replies {
+            let message = IrcMessage::try_from(format!("{}\r\n", line).as_str()).unwrap();
+            assert_eq!(message.command, command);
+            assert_eq!(String::try_from(message).unwrap(), format!("{}\r\n", line));
+        }
+
+        assert_eq!(IrcMessage::try_from(":irc.example.net 317 Jimmy Ferris soon :seconds idle\r\n"), Err(Error::Invalid));
+        assert_eq!(IrcMessage::try_from(":irc.example.net 330 Jimmy Ferris :is logged in as\r\n"), Err(Error::Invalid));
+    }
+
+    #[test]
+    fn presence() {
+        let s = |m: &str| m.to_string();
+
+        let replies = [
+            (":irc.example.net 600 Jimmy Ferris ferris crab.example.com 1700000000 :logged online", IrcCommand::RplLogOn(s("Jimmy"), s("Ferris"), s("ferris"), s("crab.example.com"), 1700000000, s("logged online"))),
+            (":irc.example.net 601 Jimmy Ferris ferris crab.example.com 1700000000 :logged offline", IrcCommand::RplLogOff(s("Jimmy"), s("Ferris"), s("ferris"), s("crab.example.com"), 1700000000, s("logged offline"))),
+            (":irc.example.net 604 Jimmy Ferris ferris crab.example.com 1700000000 :is online", IrcCommand::RplNowOn(s("Jimmy"), s("Ferris"), s("ferris"), s("crab.example.com"), 1700000000, s("is online"))),
+            (":irc.example.net 605 Jimmy Ferris * * 0 :is offline", IrcCommand::RplNowOff(s("Jimmy"), s("Ferris"), s("*"), s("*"), 0, s("is offline"))),
+            (":irc.example.net 730 Jimmy :Ferris!ferris@crab.example.com,Bobby", IrcCommand::RplMonOnline(s("Jimmy"), vec![s("Ferris!ferris@crab.example.com"), s("Bobby")])),
+            (":irc.example.net 731 Jimmy :Ferris", IrcCommand::RplMonOffline(s("Jimmy"), vec![s("Ferris")])),
+        ];
+
+        for (line, command) in replies {
+            let message = IrcMessage::try_from(format!("{}\r\n", line).as_str()).unwrap();
+            assert_eq!(message.command, command);
+            assert_eq!(String::try_from(message).unwrap(), format!("{}\r\n", line));
+        }
+
+        assert_eq!(IrcMessage::try_from(":irc.example.net 600 Jimmy Ferris ferris crab.example.com :logged online\r\n"), Err(Error::Invalid));
+    }
+
+    #[test]
+    fn away() {
+        assert_eq!(":Jimmy!jim@example.com AWAY :Gone fishing\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("Jimmy!jim@example.com".to_string()),
+            command: IrcCommand::Away(Some("Gone fishing".to_string())),
+        }));
+
+        assert_eq!(":Jimmy!jim@example.com AWAY\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("Jimmy!jim@example.com".to_string()),
+            command: IrcCommand::Away(None),
+        }));
+
+        assert_eq!(String::try_from(IrcCommand::Away(Some("Gone fishing".to_string()))).unwrap(), "AWAY :Gone fishing".to_string());
+        assert_eq!(String::try_from(IrcCommand::Away(None)).unwrap(), "AWAY".to_string());
+    }
+
+    #[test]
+    fn setname() {
+        assert_eq!(":Jimmy!jim@example.com SETNAME :James Bond\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("Jimmy!jim@example.com".to_string()),
+            command: IrcCommand::SetName("James Bond".to_string()),
+        }));
+
+        assert_eq!(String::try_from(IrcCommand::SetName("James Bond".to_string())).unwrap(), "SETNAME :James Bond".to_string());
+    }
+
+    #[test]
+    fn membership() {
+        assert_eq!(":Jimmy!jim@example.com JOIN #rust\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("Jimmy!jim@example.com".to_string()),
+            command: IrcCommand::Join("#rust".to_string(), None),
+        }));
+
+        assert_eq!(":Jimmy!jim@example.com JOIN :#rust\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("Jimmy!jim@example.com".to_string()),
+            command: IrcCommand::Join("#rust".to_string(), None),
+        }));
+
+        assert_eq!(":Jimmy!jim@example.com PART #rust :Bye\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("Jimmy!jim@example.com".to_string()),
+            command: IrcCommand::Part("#rust".to_string(), Some("Bye".to_string())),
+        }));
+
+        assert_eq!(":Bobby!bob@example.com KICK #rust Jimmy :Spam\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("Bobby!bob@example.com".to_string()),
+            command: IrcCommand::Kick("#rust".to_string(), "Jimmy".to_string(), Some("Spam".to_string())),
+        }));
+
+        assert_eq!(":Jimmy!jim@example.com QUIT :Ping timeout\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("Jimmy!jim@example.com".to_string()),
+            command: IrcCommand::Quit(Some("Ping timeout".to_string())),
+        }));
+
+        assert_eq!(String::try_from(IrcCommand::Join("0".to_string(), None)).unwrap(), "JOIN 0".to_string());
+
+        // Keys keep their position, including empty ones
+        assert_eq!(IrcCommand::try_from("JOIN #a,#b,#c key1,,key3"), Ok(IrcCommand::Join("#a,#b,#c".to_string(), Some("key1,,key3".to_string()))));
+        assert_eq!(String::try_from(IrcCommand::Join("#a,#b,#c".to_string(), Some("key1,,key3".to_string()))).unwrap(), "JOIN #a,#b,#c key1,,key3".to_string());
+        assert_eq!(String::try_from(IrcCommand::Part("#rust,#go".to_string(), None)).unwrap(), "PART #rust,#go".to_string());
+        assert_eq!(String::try_from(IrcCommand::Kick("#rust".to_string(), "Jimmy".to_string(), Some("Spam".to_string()))).unwrap(), "KICK #rust Jimmy :Spam".to_string());
+        assert_eq!(String::try_from(IrcCommand::Quit(None)).unwrap(), "QUIT".to_string());
+
+        assert_eq!(":Oper!oper@example.com KILL Jimmy :irc.example.net!Oper (Flooding)\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("Oper!oper@example.com".to_string()),
+            command: IrcCommand::Kill("Jimmy".to_string(), "irc.example.net!Oper (Flooding)".to_string()),
+        }));
+        assert_eq!(String::try_from(IrcCommand::Kill("Jimmy".to_string(), "Flooding".to_string())).unwrap(), "KILL Jimmy :Flooding".to_string());
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(":irc.example.net 353 Jimmy = #rust :@%+Jimmy +Bobby Alice\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("irc.example.net".to_string()),
+            command: IrcCommand::RplNamReply {
+                client: "Jimmy".to_string(),
+                symbol: "=".to_string(),
+                channel: "#rust".to_string(),
+                names: vec!["@%+Jimmy".to_string(), "+Bobby".to_string(), "Alice".to_string()],
+            },
+        }));
+
+        assert_eq!(":irc.example.net 366 Jimmy #rust :End of /NAMES list.\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("irc.example.net".to_string()),
+            command: IrcCommand::RplEndOfNames("Jimmy".to_string(), "#rust".to_string(), "End of /NAMES list.".to_string()),
+        }));
+    }
+
+    #[test]
+    fn client_commands() {
+        let s = |m: &str| m.to_string();
+
+        let commands = [
+            (IrcCommand::Join(s("#rust,#irc"), Some(s("hunter2"))), "JOIN #rust,#irc hunter2"),
+            (IrcCommand::Part(s("#rust"), Some(s("Bye"))), "PART #rust :Bye"),
+            (IrcCommand::Mode(s("#rust"), vec![s("+o"), s("Ferris")]), "MODE #rust +o Ferris"),
+            (IrcCommand::Topic(s("#rust"), Some(s("Rust 2.0 when"))), "TOPIC #rust :Rust 2.0 when"),
+            (IrcCommand::Topic(s("#rust"), None), "TOPIC #rust"),
+            (IrcCommand::Names(Some(s("#rust,#irc"))), "NAMES #rust,#irc"),
+            (IrcCommand::Names(None), "NAMES"),
+            (IrcCommand::List(Some(s("#rust"))), "LIST #rust"),
+            (IrcCommand::List(None), "LIST"),
+            (IrcCommand::Invite(s("Ferris"), s("#rust")), "INVITE Ferris #rust"),
+            (IrcCommand::Kick(s("#rust"), s("Ferris"), Some(s("Spam"))), "KICK #rust Ferris :Spam"),
+            (IrcCommand::Privmsg(s("#rust"), s("hello there")), "PRIVMSG #rust :hello there"),
+            (IrcCommand::Who(s("*.fi"), Some(s("o"))), "WHO *.fi o"),
+            (IrcCommand::Who(s("#rust"), None), "WHO #rust"),
+            (IrcCommand::Whois(s("Ferris"), None), "WHOIS Ferris"),
+            (IrcCommand::Whois(s("Ferris"), Some(s("Ferris"))), "WHOIS Ferris Ferris"),
+            (IrcCommand::Whowas(s("Ferris"), Some(3)), "WHOWAS Ferris 3"),
+            (IrcCommand::Kill(s("Ferris"), s("Flooding")), "KILL Ferris :Flooding"),
+            (IrcCommand::Away(Some(s("Gone fishing"))), "AWAY :Gone fishing"),
+            (IrcCommand::Quit(Some(s("Bye"))), "QUIT :Bye"),
+            (IrcCommand::Oper(s("ferris"), s("hunter2")), "OPER ferris hunter2"),
+            (IrcCommand::Wallops(s("Rebooting soon")), "WALLOPS :Rebooting soon"),
+            (IrcCommand::Ison(vec![s("Ferris"), s("Jimmy")]), "ISON Ferris Jimmy"),
+            (IrcCommand::Userhost(vec![s("Ferris"), s("Jimmy")]), "USERHOST Ferris Jimmy"),
+            (IrcCommand::Watch(vec![s("+Ferris"), s("-Jimmy")]), "WATCH +Ferris -Jimmy"),
+            (IrcCommand::Monitor(s("+"), Some(s("Ferris,Jimmy"))), "MONITOR + Ferris,Jimmy"),
+            (IrcCommand::Monitor(s("C"), None), "MONITOR C"),
+            (IrcCommand::Batch(s("+abc"), vec![s("draft/multiline"), s("#rust")]), "BATCH +abc draft/multiline #rust"),
+            (IrcCommand::Batch(s("-abc"), vec![]), "BATCH -abc"),
+        ];
+
+        for (command, line) in commands {
+            assert_eq!(String::try_from(command.clone()).unwrap(), line);
+            assert_eq!(IrcCommand::try_from(line), Ok(command));
+        }
+
+        // Forms other clients send that serialize differently
+        assert_eq!(IrcCommand::try_from("PRIVMSG #rust hello"), Ok(IrcCommand::Privmsg(s("#rust"), s("hello"))));
+        assert_eq!(IrcCommand::try_from("ISON :Ferris Jimmy"), Ok(IrcCommand::Ison(vec![s("Ferris"), s("Jimmy")])));
+        assert_eq!(IrcCommand::try_from("WHOIS irc.example.net Ferris"), Ok(IrcCommand::Whois(s("Ferris"), Some(s("irc.example.net")))));
+
+        assert_eq!(IrcCommand::try_from("PRIVMSG #rust"), Err(Error::Invalid));
+        assert_eq!(IrcCommand::try_from("WHOIS"), Err(Error::Invalid));
+        assert_eq!(IrcCommand::try_from("OPER ferris"), Err(Error::Invalid));
+    }
+
+    // The same check as the fuzz target in fuzz/, cheap enough to run with every test run
+    #[test]
+    fn parser_never_panics() {
+        let mut state: u64 = 0x9E3779B97F4A7C15;
+        let mut next = move |bound: usize| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % bound as u64) as usize
+        };
+
+        let commands = [
+            "PASS", "NICK", "USER", "PING", "PONG", "NOTICE", "ERROR", "CAP", "AWAY", "JOIN", "PART", "KICK",
+            "QUIT", "KILL", "INVITE", "CHGHOST", "ACCOUNT", "SETNAME", "TAGMSG", "TOPIC", "WHOWAS", "MODE", "AUTHENTICATE", "PRIVMSG",
+            "NAMES", "LIST", "WHO", "WHOIS", "OPER", "WALLOPS", "ISON", "USERHOST", "WATCH", "MONITOR", "BATCH",
+            "001", "002", "003", "004", "005", "251", "252", "253", "254", "255", "265", "266", "314", "324", "329",
+            "311", "312", "313", "317", "318", "319", "331", "332", "333", "346", "347", "353", "354", "366", "367", "368", "369", "372", "375", "376", "396", "600", "601", "604", "605", "730", "731", "406", "482",
+            "903", "904", "999", "", "1", "0001",
+        ];
+        let fragments = ["a", "#rust", "*", "0", "42", "-1", "99999999999999999999", ":", "é", "LS", "ACK", "@", "!", "\x00", "\r", "\n", ""];
+
+        for _ in 0..1000 {
+            let mut line = String::new();
+
+            if next(4) == 0 {
+                line.push_str(&format!("@{}={} ", fragments[next(fragments.len())], fragments[next(fragments.len())]));
+            }
+            if next(2) == 0 {
+                line.push_str(&format!(":{} ", fragments[next(fragments.len())]));
+            }
+
+            line.push_str(commands[next(commands.len())]);
+
+            for _ in 0..next(7) {
+                line.push(' ');
+                line.push_str(fragments[next(fragments.len())]);
+            }
+
+            if next(2) == 0 {
+                line.push_str(" :");
+                line.push_str(fragments[next(fragments.len())]);
+            }
+
+            if next(8) != 0 {
+                line.push_str("\r\n");
+            }
+
+            let _ = IrcMessage::try_from(line.as_str());
+            let _ = GenericIrcCommand::try_from(line.trim_end_matches("\r\n"));
+        }
+    }
+
+    #[test]
+    fn generic_round_trip() {
+        // A fixed seed keeps failures reproducible
+        let mut state: u64 = 0x2545F4914F6CDD1D;
+        let mut next = move |bound: usize| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % bound as u64) as usize
+        };
+
+        fn word(next: &mut impl FnMut(usize) -> usize, allow_space: bool, min: usize) -> String {
+            let alphabet = ['a', 'Z', '0', '#', ':', '!', '@', ',', '*', 'é'];
+            let length = min + next(8);
+
+            (0..length).map(|_| {
+                if allow_space && next(4) == 0 {
+                    ' '
+                } else {
+                    alphabet[next(alphabet.len())]
+                }
+            }).collect()
+        }
+
+        for _ in 0..1000 {
+            let params = (0..next(5)).map(|_| {
+                let param = word(&mut next, false, 1);
+                // Middle params can't start with a colon
+                param.replacen(':', "x", usize::from(param.starts_with(':')))
+            }).collect::<Vec<_>>();
+
+            let command = GenericIrcCommand {
+                command: GenericIrcCommandType::Text("PRIVMSG".to_string()),
+                params,
+                trailing: (next(2) == 0).then(|| word(&mut next, true, 0)),
+            };
+
+            let line = String::try_from(command.clone()).unwrap();
+            assert_eq!(GenericIrcCommand::try_from(line.as_str()), Ok(command), "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn trailing_params() {
+        let command = GenericIrcCommand {
+            command: GenericIrcCommandType::Text("PRIVMSG".to_string()),
+            params: vec!["#rust".to_string(), "hello there".to_string()],
+            trailing: None,
+        };
+
+        // Used to be sent as the trailing, now it has to be moved there explicitly
+        assert_eq!(String::try_from(command.clone()), Err(Error::Invalid));
+        assert_eq!(String::try_from(command.promote_trailing()).unwrap(), "PRIVMSG #rust :hello there".to_string());
+
+        for param in ["", ":hello"] {
+            let command = GenericIrcCommand {
+                command: GenericIrcCommandType::Text("PRIVMSG".to_string()),
+                params: vec!["#rust".to_string(), param.to_string()],
+                trailing: None,
+            }.promote_trailing();
+
+            assert_eq!(command.trailing, Some(param.to_string()));
+            assert_eq!(command.params, vec!["#rust".to_string()]);
+        }
+    }
+
+    #[test]
+    fn lusers_counts() {
+        assert_eq!(
+            IrcMessage::try_from(":irc.example.net 252 nick notanumber :operators\r\n"),
+            Err(Error::ParseInt("notanumber".to_string())),
+        );
+
+        assert_eq!(":irc.example.net 266 nick 5000000000 5000000001 :Current global users\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("irc.example.net".to_string()),
+            command: IrcCommand::RplGlobalUsers("nick".to_string(), Some((5000000000, 5000000001)), "Current global users".to_string()),
+        }));
+    }
+
+    #[test]
+    fn user_changes() {
+        assert_eq!(":Jimmy!jim@example.com CHGHOST jimmy vhost.example.com\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("Jimmy!jim@example.com".to_string()),
+            command: IrcCommand::ChgHost("jimmy".to_string(), "vhost.example.com".to_string()),
+        }));
+
+        assert_eq!(":Jimmy!jim@example.com ACCOUNT *\r\n".try_into(), Ok(IrcMessage {
+            tags: vec![],
+            prefix: Some("Jimmy!jim@example.com".to_string()),
+            command: IrcCommand::Account("*".to_string()),
+        }));
+
+        assert_eq!(String::try_from(IrcCommand::ChgHost("jimmy".to_string(), "vhost.example.com".to_string())).unwrap(), "CHGHOST jimmy vhost.example.com".to_string());
+    }
+
+    #[test]
+    fn fixtures() {
+        use crate::fixtures::*;
+
+        for line in [inspircd::ALL, solanum::ALL, unrealircd::ALL, ngircd::ALL].concat() {
+            match IrcMessage::try_from(line) {
+                Ok(IrcMessage { command: IrcCommand::Generic(command), .. }) => panic!("Parsed as generic: {:?}", command),
+                Ok(_) => {},
+                Err(e) => panic!("Failed to parse {:?}: {}", line, e),
+            }
+        }
     }
 }
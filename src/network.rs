@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::task::JoinSet;
+
+use crate::client::Client;
+use crate::client::ClientBuilder;
+use crate::context::Context;
+use crate::event::Event;
+use crate::event_handler::EventHandler;
+use crate::message::IrcCommand;
+
+pub trait NetworkEventHandler: Send + Sync {
+    fn on_event(&self, network: &str, ctx: Arc<Context>, event: Event) {
+        let _ = network;
+        let _ = event;
+        let _ = ctx;
+    }
+}
+
+// Forwards a client's events to the network handlers, tagged with the network name
+struct NetworkTag {
+    network: String,
+    event_handlers: Vec<Arc<dyn NetworkEventHandler>>,
+}
+
+impl EventHandler for NetworkTag {
+    fn on_event(&self, ctx: Arc<Context>, event: Event) {
+        for event_handler in self.event_handlers.iter() {
+            event_handler.on_event(&self.network, ctx.clone(), event.clone());
+        }
+    }
+}
+
+// Manages clients connected to several networks, keyed by network name
+#[derive(Default)]
+pub struct Network {
+    clients: HashMap<String, Client>,
+    event_handlers: Vec<Arc<dyn NetworkEventHandler>>,
+}
+
+impl Network {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Only applies to networks added afterwards
+    pub fn with_event_handler<H: NetworkEventHandler + 'static>(mut self, event_handler: H) -> Self {
+        self.event_handlers.push(Arc::new(event_handler));
+        self
+    }
+
+    pub async fn add_network(&mut self, name: &str, builder: ClientBuilder) -> Result<(), std::io::Error> {
+        let client = builder.with_event_handler(NetworkTag {
+            network: name.to_string(),
+            event_handlers: self.event_handlers.clone(),
+        }).await?;
+
+        self.clients.insert(name.to_string(), client);
+
+        Ok(())
+    }
+
+    pub fn client(&self, name: &str) -> Option<&Client> {
+        self.clients.get(name)
+    }
+
+    pub fn networks(&self) -> impl Iterator<Item = &String> {
+        self.clients.keys()
+    }
+
+    // Connects every network concurrently
+    pub async fn connect(&mut self) -> HashMap<String, Result<(), std::io::Error>> {
+        let mut connecting = JoinSet::new();
+
+        for (name, mut client) in self.clients.drain() {
+            connecting.spawn(async move {
+                let result = client.connect().await;
+                (name, client, result)
+            });
+        }
+
+        let mut results = HashMap::new();
+
+        while let Some(connected) = connecting.join_next().await {
+            // The tasks are never cancelled, so an error can only be a panic from connect
+            let (name, client, result) = match connected {
+                Ok(connected) => connected,
+                Err(e) => std::panic::resume_unwind(e.into_panic()),
+            };
+
+            self.clients.insert(name.clone(), client);
+            results.insert(name, result);
+        }
+
+        results
+    }
+
+    pub async fn send(&self, network: &str, command: IrcCommand) -> Result<(), std::io::Error> {
+        match self.clients.get(network) {
+            Some(client) => client.send(command).await,
+            None => Err(std::io::Error::new(std::io::ErrorKind::NotFound, format!("Unknown network \"{}\"", network))),
+        }
+    }
+
+    pub async fn broadcast(&self, command: IrcCommand) -> HashMap<String, Result<(), std::io::Error>> {
+        let mut results = HashMap::new();
+
+        for (name, client) in self.clients.iter() {
+            results.insert(name.clone(), client.send(command.clone()).await);
+        }
+
+        results
+    }
+}
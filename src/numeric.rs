@@ -0,0 +1,82 @@
+// Names for the error numerics most clients react to, e.g. in Event::ServerError
+
+pub const ERR_NOSUCHNICK: u16 = 401;
+pub const ERR_NOSUCHSERVER: u16 = 402;
+pub const ERR_NOSUCHCHANNEL: u16 = 403;
+pub const ERR_CANNOTSENDTOCHAN: u16 = 404;
+pub const ERR_WASNOSUCHNICK: u16 = 406;
+pub const ERR_UNKNOWNCOMMAND: u16 = 421;
+pub const ERR_NICKNAMEINUSE: u16 = 433;
+pub const ERR_NOTONCHANNEL: u16 = 442;
+pub const ERR_NOTREGISTERED: u16 = 451;
+pub const ERR_NEEDMOREPARAMS: u16 = 461;
+pub const ERR_ALREADYREGISTERED: u16 = 462;
+pub const ERR_PASSWDMISMATCH: u16 = 464;
+pub const ERR_CHANNELISFULL: u16 = 471;
+pub const ERR_UNKNOWNMODE: u16 = 472;
+pub const ERR_INVITEONLYCHAN: u16 = 473;
+pub const ERR_BANNEDFROMCHAN: u16 = 474;
+pub const ERR_BADCHANNELKEY: u16 = 475;
+pub const ERR_NOPRIVILEGES: u16 = 481;
+pub const ERR_CHANOPRIVSNEEDED: u16 = 482;
+pub const ERR_USERSDONTMATCH: u16 = 502;
+
+// Errors are 400 through 599 by convention. Later extensions put a few outside that range
+pub fn numeric_is_error(code: u16) -> bool {
+    matches!(code,
+        400..=599
+        | 691 // ERR_STARTTLS
+        | 696 // ERR_INVALIDMODEPARAM
+        | 723 // ERR_NOPRIVS
+        | 734 // ERR_MONLISTFULL
+        | 902 // ERR_NICKLOCKED
+        | 904..=907 // ERR_SASLFAIL, ERR_SASLTOOLONG, ERR_SASLABORTED, ERR_SASLALREADY
+    )
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors() {
+        let known = [
+            (ERR_NOSUCHNICK, 401),
+            (ERR_NOSUCHSERVER, 402),
+            (ERR_NOSUCHCHANNEL, 403),
+            (ERR_CANNOTSENDTOCHAN, 404),
+            (ERR_WASNOSUCHNICK, 406),
+            (ERR_UNKNOWNCOMMAND, 421),
+            (ERR_NICKNAMEINUSE, 433),
+            (ERR_NOTONCHANNEL, 442),
+            (ERR_NOTREGISTERED, 451),
+            (ERR_NEEDMOREPARAMS, 461),
+            (ERR_ALREADYREGISTERED, 462),
+            (ERR_PASSWDMISMATCH, 464),
+            (ERR_CHANNELISFULL, 471),
+            (ERR_UNKNOWNMODE, 472),
+            (ERR_INVITEONLYCHAN, 473),
+            (ERR_BANNEDFROMCHAN, 474),
+            (ERR_BADCHANNELKEY, 475),
+            (ERR_NOPRIVILEGES, 481),
+            (ERR_CHANOPRIVSNEEDED, 482),
+            (ERR_USERSDONTMATCH, 502),
+        ];
+
+        for (constant, code) in known {
+            assert_eq!(constant, code);
+            assert!(numeric_is_error(constant));
+        }
+
+        for code in [904, 907, 696] {
+            assert!(numeric_is_error(code));
+        }
+
+        // RPL_WELCOME, RPL_TOPIC, RPL_LOGGEDIN and RPL_SASLSUCCESS
+        for code in [1, 332, 900, 903] {
+            assert!(!numeric_is_error(code));
+        }
+    }
+}
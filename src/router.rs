@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::context::Context;
+use crate::event::Event;
+use crate::event_handler::EventHandler;
+use crate::message::GenericIrcCommand;
+use crate::message::IrcMessage;
+
+type CommandCallback = Box<dyn Fn(Arc<Context>, &IrcMessage) + Send + Sync>;
+
+// Dispatches raw messages to callbacks registered per command, e.g. "PRIVMSG" or "001"
+#[derive(Default)]
+pub struct CommandRouter {
+    routes: HashMap<String, Vec<CommandCallback>>,
+    fallback: Option<CommandCallback>,
+}
+
+impl CommandRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn on<F: Fn(Arc<Context>, &IrcMessage) + Send + Sync + 'static>(mut self, command: &str, callback: F) -> Self {
+        self.routes.entry(command.to_ascii_uppercase()).or_default().push(Box::new(callback));
+        self
+    }
+
+    pub fn on_numeric<F: Fn(Arc<Context>, &IrcMessage) + Send + Sync + 'static>(self, numeric: u16, callback: F) -> Self {
+        self.on(&format!("{:03}", numeric), callback)
+    }
+
+    // Called for messages that no other route matched
+    pub fn fallback<F: Fn(Arc<Context>, &IrcMessage) + Send + Sync + 'static>(mut self, callback: F) -> Self {
+        self.fallback = Some(Box::new(callback));
+        self
+    }
+
+    pub fn dispatch(&self, ctx: Arc<Context>, message: &IrcMessage) {
+        let command = String::from(GenericIrcCommand::from(message.command.clone()).command);
+
+        match self.routes.get(&command) {
+            Some(callbacks) => {
+                for callback in callbacks {
+                    callback(ctx.clone(), message);
+                }
+            },
+            None => {
+                if let Some(fallback) = &self.fallback {
+                    fallback(ctx, message);
+                }
+            },
+        }
+    }
+}
+
+impl EventHandler for CommandRouter {
+    fn on_event(&self, ctx: Arc<Context>, event: Event) {
+        if let Event::RawMessage(message) = event {
+            self.dispatch(ctx, &message);
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use crate::client::Motd;
+    use crate::context::ConnectionStatus;
+
+    use super::*;
+
+    #[test]
+    fn dispatch() {
+        let notices = Arc::new(AtomicUsize::new(0));
+        let welcomes = Arc::new(AtomicUsize::new(0));
+        let unhandled = Arc::new(AtomicUsize::new(0));
+
+        let router = {
+            let notices = notices.clone();
+            let welcomes = welcomes.clone();
+            let unhandled = unhandled.clone();
+
+            CommandRouter::new()
+                .on("notice", move |_, _| { notices.fetch_add(1, Ordering::SeqCst); })
+                .on_numeric(1, move |_, _| { welcomes.fetch_add(1, Ordering::SeqCst); })
+                .fallback(move |_, _| { unhandled.fetch_add(1, Ordering::SeqCst); })
+        };
+
+        let ctx = Arc::new(Context::new(ConnectionStatus::Connected, Motd::Empty));
+
+        for line in [
+            ":irc.example.net NOTICE * :*** Looking up your hostname...\r\n",
+            ":irc.example.net 001 Jimmy :Welcome to the network\r\n",
+            ":Jimmy!jim@example.com PRIVMSG #rust :hello\r\n",
+        ] {
+            router.on_event(ctx.clone(), Event::RawMessage(IrcMessage::try_from(line).unwrap()));
+        }
+
+        assert_eq!(notices.load(Ordering::SeqCst), 1);
+        assert_eq!(welcomes.load(Ordering::SeqCst), 1);
+        assert_eq!(unhandled.load(Ordering::SeqCst), 1);
+    }
+}
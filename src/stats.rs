@@ -0,0 +1,128 @@
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+
+// Snapshot of the counters for the current connection, they start over on reconnect
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ConnectionStats {
+    pub messages_sent: u64,
+    pub messages_received: u64,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub connected_at: Option<SystemTime>,
+    // Round trip of the last PING we sent that the server answered
+    pub latency: Option<Duration>,
+}
+
+// Network size from the LUSERS replies (251 through 255, 265 and 266), sent on registration
+// and in answer to LUSERS. Counts the server didn't send are None
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct LUserStats {
+    // Visible and invisible users on the whole network
+    pub clients: Option<u64>,
+    pub ops: Option<u64>,
+    // Connections that haven't registered yet
+    pub unknown: Option<u64>,
+    pub channels: Option<u64>,
+    // Current and maximum
+    pub local_users: Option<(u64, u64)>,
+    pub global_users: Option<(u64, u64)>,
+}
+
+impl LUserStats {
+    // RPL_LUSERCLIENT only has text, e.g. "There are 3 users and 12 invisible on 2 servers"
+    pub(crate) fn parse_clients(text: &str) -> Option<u64> {
+        let words = text.split_whitespace().collect::<Vec<_>>();
+        let visible = words.iter().find_map(|m| m.parse::<u64>().ok())?;
+        let invisible = words.windows(2).find(|m| m[1] == "invisible").and_then(|m| m[0].parse::<u64>().ok());
+
+        Some(visible + invisible.unwrap_or(0))
+    }
+
+    // For 265 and 266 from servers that only put the counts in the text, e.g.
+    // "Current local users: 3  Max: 5"
+    pub(crate) fn parse_current_max(text: &str) -> Option<(u64, u64)> {
+        let mut counts = text.split_whitespace().filter_map(|m| m.trim_end_matches(',').parse::<u64>().ok());
+
+        Some((counts.next()?, counts.next()?))
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct StatsCounters {
+    messages_sent: AtomicU64,
+    messages_received: AtomicU64,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    connected_at: Mutex<Option<SystemTime>>,
+    latency: Mutex<Option<Duration>>,
+    pending_ping: Mutex<Option<(String, Instant)>>,
+}
+
+impl StatsCounters {
+    pub(crate) fn reset(&self) {
+        self.messages_sent.store(0, Ordering::Relaxed);
+        self.messages_received.store(0, Ordering::Relaxed);
+        self.bytes_sent.store(0, Ordering::Relaxed);
+        self.bytes_received.store(0, Ordering::Relaxed);
+        *self.connected_at.lock().unwrap() = Some(SystemTime::now());
+        *self.latency.lock().unwrap() = None;
+        *self.pending_ping.lock().unwrap() = None;
+    }
+
+    pub(crate) fn sent(&self, bytes: usize) {
+        self.messages_sent.fetch_add(1, Ordering::Relaxed);
+        self.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn received(&self, bytes: usize) {
+        self.messages_received.fetch_add(1, Ordering::Relaxed);
+        self.bytes_received.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn ping_sent(&self, token: &str) {
+        *self.pending_ping.lock().unwrap() = Some((token.to_string(), Instant::now()));
+    }
+
+    // Only a PONG echoing the token of the last PING counts, returns the round trip if it does
+    pub(crate) fn pong_received(&self, token: &str) -> Option<Duration> {
+        let mut pending_ping = self.pending_ping.lock().unwrap();
+
+        let (_, sent_at) = pending_ping.take_if(|(pending, _)| pending == token)?;
+        let latency = sent_at.elapsed();
+        *self.latency.lock().unwrap() = Some(latency);
+
+        Some(latency)
+    }
+
+    pub(crate) fn snapshot(&self) -> ConnectionStats {
+        ConnectionStats {
+            messages_sent: self.messages_sent.load(Ordering::Relaxed),
+            messages_received: self.messages_received.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            connected_at: *self.connected_at.lock().unwrap(),
+            latency: *self.latency.lock().unwrap(),
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn luser_text() {
+        assert_eq!(LUserStats::parse_clients("There are 3 users and 12 invisible on 2 servers"), Some(15));
+        assert_eq!(LUserStats::parse_clients("There are 3 users on 1 server"), Some(3));
+        assert_eq!(LUserStats::parse_clients("No numbers here"), None);
+
+        assert_eq!(LUserStats::parse_current_max("Current local users: 3  Max: 5"), Some((3, 5)));
+        assert_eq!(LUserStats::parse_current_max("Current global users 1,234"), None);
+    }
+}
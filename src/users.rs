@@ -0,0 +1,99 @@
+use std::time::Duration;
+use std::time::SystemTime;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct WhowasEntry {
+    pub nick: String,
+    pub username: String,
+    pub host: String,
+    pub realname: String,
+}
+
+// What we've heard about someone's presence from away-notify, account-notify, WHOX replies
+// and tracked nicks. Only users something was heard about are known, see Client::user
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct User {
+    pub nick: String,
+    pub username: Option<String>,
+    pub host: Option<String>,
+    // The away message. Empty when a WHOX reply's flags showed the user away without one
+    pub away: Option<String>,
+    // Services account, None when logged out
+    pub account: Option<String>,
+}
+
+// WHOX field letters in the order servers send them, "t" is the query token
+pub(crate) const WHOX_FIELDS: &str = "tcuihsnfdlaor";
+
+// One RPL_WHOSPCRPL (354) line. Fields the query didn't ask for are None
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct WhoxEntry {
+    pub channel: Option<String>,
+    pub user: Option<String>,
+    pub ip: Option<String>,
+    pub host: Option<String>,
+    pub server: Option<String>,
+    pub nick: Option<String>,
+    // e.g. "H@", "G" when away, "*" for IRC operators
+    pub flags: Option<String>,
+    pub hopcount: Option<u32>,
+    // Seconds, servers may report 0 for users on other servers
+    pub idle: Option<u64>,
+    // None when the user isn't logged in
+    pub account: Option<String>,
+    pub oplevel: Option<String>,
+    pub realname: Option<String>,
+}
+
+impl WhoxEntry {
+    // `fields` are the requested letters without "t", in WHOX_FIELDS order, matching `values`
+    pub(crate) fn parse(fields: &str, values: Vec<String>) -> Self {
+        let mut entry = Self::default();
+
+        for (field, value) in fields.chars().zip(values) {
+            match field {
+                'c' => entry.channel = Some(value),
+                'u' => entry.user = Some(value),
+                'i' => entry.ip = Some(value),
+                'h' => entry.host = Some(value),
+                's' => entry.server = Some(value),
+                'n' => entry.nick = Some(value),
+                'f' => entry.flags = Some(value),
+                'd' => entry.hopcount = value.parse().ok(),
+                'l' => entry.idle = value.parse().ok(),
+                'a' => entry.account = Some(value).filter(|m| m != "0"),
+                'o' => entry.oplevel = Some(value),
+                'r' => entry.realname = Some(value),
+                _ => {},
+            }
+        }
+
+        entry
+    }
+
+    // Only known when the flags were requested
+    pub fn is_away(&self) -> Option<bool> {
+        self.flags.as_ref().map(|m| m.starts_with('G'))
+    }
+}
+
+// Everything a WHOIS reply said about a user, collected up to RPL_ENDOFWHOIS (318)
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct WhoisInfo {
+    pub nick: String,
+    pub username: Option<String>,
+    pub host: Option<String>,
+    pub realname: Option<String>,
+    // The server the user is connected to, and its description
+    pub server: Option<String>,
+    pub server_info: Option<String>,
+    pub operator: bool,
+    // Services account from RPL_WHOISACCOUNT (330), None when not logged in
+    pub account: Option<String>,
+    // From RPL_WHOISIDLE (317), which only the user's own server sends. Not every server
+    // includes the signon time
+    pub idle: Option<Duration>,
+    pub signon: Option<SystemTime>,
+    // With their status prefixes, e.g. "@#rust"
+    pub channels: Vec<String>,
+}